@@ -0,0 +1,52 @@
+use std::fmt::Display;
+use std::iter;
+use std::time::Instant;
+
+use rand::random;
+use slipstream::dispatch::best_instruction_set;
+use slipstream::prelude::*;
+
+const SIZE: usize = 1024 * 1024;
+type V = f32x8;
+
+fn dot_prod_scalar(l: &[f32], r: &[f32]) -> f32 {
+    l.iter().zip(r).map(|(l, r)| l * r).sum()
+}
+
+#[inline(always)]
+fn dot_prod_compile_time(l: &[f32], r: &[f32]) -> f32 {
+    (l, r)
+        .vectorize()
+        .map(|(l, r): (V, V)| l * r)
+        .sum::<V>()
+        .horizontal_sum()
+}
+
+slipstream::dispatch! {
+    fn dot_prod_run_time(l: &[f32], r: &[f32]) -> f32 {
+        dot_prod_compile_time(l, r)
+    }
+}
+
+fn timed<N: Display, R, F: FnOnce() -> R>(name: N, f: F) -> R {
+    let now = Instant::now();
+    let result = f();
+    println!("{} took:\t{:?}", name, now.elapsed());
+    result
+}
+
+fn main() {
+    println!("Detected instruction set: {:?}", best_instruction_set());
+    // Small integers, so the result is exact regardless of the order of additions.
+    let l = iter::repeat_with(|| (random::<u8>() % 4) as f32)
+        .take(SIZE)
+        .collect::<Vec<_>>();
+    let r = iter::repeat_with(|| (random::<u8>() % 4) as f32)
+        .take(SIZE)
+        .collect::<Vec<_>>();
+    let z = timed("Scalar", || dot_prod_scalar(&l, &r));
+    let x = timed("Compile-time detected", || dot_prod_compile_time(&l, &r));
+    let w = timed("Run-time detected", || dot_prod_run_time(&l, &r));
+    assert_eq!(z, x);
+    assert_eq!(z, w);
+}
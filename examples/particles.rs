@@ -0,0 +1,60 @@
+//! Simulation of particles flying around, stored as a struct of arrays.
+
+use slipstream::prelude::*;
+use slipstream::Vectorize;
+
+type V = f32x8;
+
+const COUNT: usize = 1024;
+const STEPS: usize = 100;
+const DT: f32 = 0.01;
+const GRAVITY: f32 = -9.81;
+
+#[derive(Vectorize)]
+#[vectorize(f32x8)]
+struct Particles {
+    x: Vec<f32>,
+    y: Vec<f32>,
+    vx: Vec<f32>,
+    vy: Vec<f32>,
+}
+
+impl Particles {
+    fn new() -> Self {
+        let init = |f: fn(usize) -> f32| (0..COUNT).map(f).collect::<Vec<_>>();
+        Particles {
+            x: init(|i| i as f32),
+            y: init(|_| 0.0),
+            vx: init(|i| (i % 7) as f32 - 3.0),
+            vy: init(|i| (i % 13) as f32),
+        }
+    }
+
+    fn step(&mut self) {
+        let dt = V::splat(DT);
+        let g = V::splat(GRAVITY * DT);
+        for mut p in self.vectorize_mut() {
+            *p.vy += g;
+            *p.x = p.vx.mul_add(dt, *p.x);
+            *p.y = p.vy.mul_add(dt, *p.y);
+        }
+    }
+
+    fn average_height(&self) -> f32 {
+        self.vectorize().map(|p| p.y).sum::<V>().horizontal_sum() / COUNT as f32
+    }
+}
+
+fn main() {
+    let mut particles = Particles::new();
+    for step in 0..STEPS {
+        particles.step();
+        if step % 10 == 9 {
+            println!(
+                "Step {}: average height {:.3}",
+                step + 1,
+                particles.average_height()
+            );
+        }
+    }
+}
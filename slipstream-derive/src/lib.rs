@@ -0,0 +1,247 @@
+#![doc(test(attr(deny(warnings))))]
+#![forbid(unsafe_code)]
+#![warn(missing_docs)]
+
+//! Derive macros for the [`slipstream`] crate.
+//!
+//! These are not meant to be used directly. Enable the `derive` feature of `slipstream` and use
+//! them through it.
+//!
+//! [`slipstream`]: https://docs.rs/slipstream
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident, Type};
+
+/// The number of fields the tuple [`Vectorizable`] implementations support.
+///
+/// [`Vectorizable`]: https://docs.rs/slipstream/*/slipstream/trait.Vectorizable.html
+const MAX_FIELDS: usize = 10;
+
+fn vector_type(attrs: &[Attribute]) -> Result<Option<Type>, Error> {
+    let mut result = None;
+    for attr in attrs.iter().filter(|a| a.path.is_ident("vectorize")) {
+        if result.is_some() {
+            return Err(Error::new_spanned(attr, "Duplicate vectorize attribute"));
+        }
+        result = Some(attr.parse_args()?);
+    }
+    Ok(result)
+}
+
+fn derive_vectorize(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let vis = &input.vis;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Vectorize can't be derived for generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input,
+                    "Vectorize can be derived only for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                "Vectorize can be derived only for structs",
+            ))
+        }
+    };
+    if fields.is_empty() || fields.len() > MAX_FIELDS {
+        return Err(Error::new_spanned(
+            fields,
+            format!("Vectorize supports 1 to {} fields", MAX_FIELDS),
+        ));
+    }
+    let default = vector_type(&input.attrs)?;
+
+    let mut idents = Vec::new();
+    let mut vises = Vec::new();
+    let mut types = Vec::new();
+    for field in fields {
+        let ty = match (vector_type(&field.attrs)?, &default) {
+            (Some(ty), _) => ty,
+            (None, Some(ty)) => ty.clone(),
+            (None, None) => {
+                return Err(Error::new_spanned(
+                    field,
+                    "Missing #[vectorize(VectorType)] attribute on the field or the struct",
+                ))
+            }
+        };
+        idents.push(field.ident.clone().expect("Named field without a name"));
+        vises.push(&field.vis);
+        types.push(ty);
+    }
+
+    let lifetime = quote!('slipstream_vectorize);
+    let proxies = types
+        .iter()
+        .map(|ty| {
+            quote! {
+                ::slipstream::iterators::MutProxy<
+                    #lifetime,
+                    <#ty as ::core::ops::Index<usize>>::Output,
+                    #ty
+                >
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // A single field is vectorized directly, more of them as a tuple.
+    let (read, write, pattern, types_tuple, proxies_tuple) = if idents.len() == 1 {
+        let ident = &idents[0];
+        (
+            quote!(&self.#ident[..]),
+            quote!(&mut self.#ident[..]),
+            quote!(#ident),
+            quote!(#(#types)*),
+            quote!(#(#proxies)*),
+        )
+    } else {
+        (
+            quote!((#(&self.#idents[..]),*)),
+            quote!((#(&mut self.#idents[..]),*)),
+            quote!((#(#idents),*)),
+            quote!((#(#types),*)),
+            quote!((#(#proxies),*)),
+        )
+    };
+
+    let vectors = format_ident!("{}Vector", name);
+    let vectors_mut = format_ident!("{}VectorMut", name);
+    let vectors_doc = format!(
+        "One vector worth of [`{}`], as produced by its `vectorize` method.",
+        name
+    );
+    let vectors_mut_doc = format!(
+        "One vector worth of [`{}`], as produced by its `vectorize_mut` method.\n\n\
+         The changes are written back when the fields are dropped.",
+        name
+    );
+    let slices = Ident::new("slices", Span::call_site());
+
+    Ok(quote! {
+        #[doc = #vectors_doc]
+        #[derive(Copy, Clone, Debug)]
+        #[allow(dead_code)]
+        #vis struct #vectors {
+            #(
+                #[allow(missing_docs)]
+                #vises #idents: #types,
+            )*
+        }
+
+        #[doc = #vectors_mut_doc]
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        #vis struct #vectors_mut<#lifetime> {
+            #(
+                #[allow(missing_docs)]
+                #vises #idents: #proxies,
+            )*
+        }
+
+        impl #name {
+            /// Iterates over the fields in tandem, split into vectors.
+            ///
+            /// # Panics
+            ///
+            /// If the fields are not divisible into whole vectors or if they produce different
+            /// number of vectors.
+            #[inline]
+            #[allow(dead_code)]
+            #vis fn vectorize(&self) -> impl ::core::iter::Iterator<Item = #vectors> + '_ {
+                let #slices = #read;
+                <_ as ::slipstream::Vectorizable<#types_tuple>>::vectorize(#slices)
+                    .map(|#pattern| #vectors { #(#idents),* })
+            }
+
+            /// Iterates over the fields in tandem, split into vectors that can be modified.
+            ///
+            /// # Panics
+            ///
+            /// If the fields are not divisible into whole vectors or if they produce different
+            /// number of vectors.
+            #[inline]
+            #[allow(dead_code)]
+            #vis fn vectorize_mut<#lifetime>(
+                &#lifetime mut self,
+            ) -> impl ::core::iter::Iterator<Item = #vectors_mut<#lifetime>> + #lifetime {
+                let #slices = #write;
+                <_ as ::slipstream::Vectorizable<#proxies_tuple>>::vectorize(#slices)
+                    .map(|#pattern| #vectors_mut { #(#idents),* })
+            }
+        }
+    })
+}
+
+/// Derives vectorized iteration over a struct of arrays.
+///
+/// The struct must have named fields that can be indexed by `..` (eg. `Vec`s, arrays or slices).
+/// The type of vectors to split them into is specified by the `#[vectorize(VectorType)]`
+/// attribute, either on the whole struct or on individual fields (the field one takes
+/// precedence).
+///
+/// For a struct `Name`, this generates:
+///
+/// * A `NameVector` struct with the same fields, but holding one vector each.
+/// * A `NameVectorMut` struct, with the fields holding a `MutProxy` each.
+/// * The `vectorize` and `vectorize_mut` methods on `Name`, returning iterators of these.
+///
+/// The iteration goes over all the fields in tandem, using the tuple implementation of
+/// `Vectorizable`. Therefore, the same rules about lengths apply ‒ the fields don't have to be of
+/// the same length, but must produce the same number of vectors. There can be at most 10 fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use slipstream::prelude::*;
+/// use slipstream::Vectorize;
+///
+/// #[derive(Vectorize)]
+/// #[vectorize(f32x4)]
+/// struct Points {
+///     x: Vec<f32>,
+///     y: Vec<f32>,
+///     #[vectorize(u32x4)]
+///     id: Vec<u32>,
+/// }
+///
+/// let mut points = Points {
+///     x: vec![1.0; 8],
+///     y: vec![2.0; 8],
+///     id: (0..8).collect(),
+/// };
+///
+/// for mut p in points.vectorize_mut() {
+///     *p.x += *p.y;
+///     *p.id *= 2;
+/// }
+///
+/// let total = points
+///     .vectorize()
+///     .map(|p| p.x)
+///     .sum::<f32x4>()
+///     .horizontal_sum();
+/// assert_eq!(total, 24.0);
+/// assert_eq!(points.id[7], 14);
+/// ```
+#[proc_macro_derive(Vectorize, attributes(vectorize))]
+pub fn vectorize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_vectorize(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
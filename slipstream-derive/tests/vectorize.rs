@@ -0,0 +1,40 @@
+use slipstream::prelude::*;
+use slipstream::Vectorize;
+
+#[derive(Vectorize)]
+struct Single {
+    #[vectorize(u16x4)]
+    data: [u16; 8],
+}
+
+#[derive(Vectorize)]
+#[vectorize(u32x2)]
+struct Pair {
+    a: Vec<u32>,
+    b: Vec<u32>,
+}
+
+#[test]
+fn single() {
+    let mut single = Single {
+        data: [1, 2, 3, 4, 5, 6, 7, 8],
+    };
+    for mut s in single.vectorize_mut() {
+        *s.data *= 2;
+    }
+    let all = single.vectorize().map(|s| s.data).collect::<Vec<_>>();
+    assert_eq!(
+        all,
+        [u16x4::new([2, 4, 6, 8]), u16x4::new([10, 12, 14, 16])]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Vectorizing data of different lengths")]
+fn different_lengths() {
+    let pair = Pair {
+        a: vec![1; 4],
+        b: vec![1; 6],
+    };
+    pair.vectorize().for_each(drop);
+}
@@ -0,0 +1,143 @@
+//! Reading vectors out of raw bytes with explicit endianness.
+//!
+//! Binary formats and network protocols often pack several fields of different types next to each
+//! other, each of them in a fixed byte order. The [`FromBytesAt`] trait reads a vector (or a tuple
+//! of vectors) from given offsets of a byte buffer, converting the byte order of each field as
+//! needed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use slipstream::prelude::*;
+//! use slipstream::bytes::{Endian, FromBytesAt};
+//!
+//! // Four big-endian 16-bit ports, followed by two little-endian 32-bit counters.
+//! let header = [
+//!     0x00, 0x50, 0x01, 0xBB, 0x1F, 0x90, 0x00, 0x16,
+//!     0x01, 0x00, 0x00, 0x00, 0xFF, 0x00, 0x00, 0x00,
+//! ];
+//! let (ports, counters) = <(u16x4, u32x2)>::from_bytes_at(
+//!     &header,
+//!     ((0, Endian::Big), (8, Endian::Little)),
+//! );
+//! assert_eq!(ports, u16x4::new([80, 443, 8080, 22]));
+//! assert_eq!(counters, u32x2::new([1, 255]));
+//! ```
+
+use core::mem;
+
+use num_traits::PrimInt;
+
+use crate::inner::Pod;
+use crate::vector::align::Align;
+use crate::Vector;
+
+/// Byte order of the lanes in the input bytes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Endian {
+    /// The most significant byte first (the network byte order).
+    Big,
+    /// The least significant byte first.
+    Little,
+}
+
+/// Types that can be read from a byte buffer at a given position.
+///
+/// This is implemented for integer vectors and for tuples of them. The tuples allow reading
+/// several differently typed fields of a header at once.
+pub trait FromBytesAt: Sized {
+    /// Where to read the value from.
+    ///
+    /// For a vector, this is the offset of the first byte and the byte order of the lanes. For a
+    /// tuple, it is a tuple of the layouts of the fields.
+    type Layout;
+
+    /// Reads the value from the bytes.
+    ///
+    /// The bytes don't need to be aligned in any way.
+    ///
+    /// # Panics
+    ///
+    /// If any of the fields reaches past the end of the bytes.
+    fn from_bytes_at(bytes: &[u8], layout: Self::Layout) -> Self;
+}
+
+impl<A, B, const S: usize> FromBytesAt for Vector<A, B, S>
+where
+    A: Align,
+    B: Pod + PrimInt,
+{
+    type Layout = (usize, Endian);
+
+    #[inline]
+    fn from_bytes_at(bytes: &[u8], (offset, endian): (usize, Endian)) -> Self {
+        let size = mem::size_of::<Self>();
+        assert!(
+            offset <= bytes.len() && bytes.len() - offset >= size,
+            "Field at {} of size {} doesn't fit into {} bytes",
+            offset,
+            size,
+            bytes.len(),
+        );
+        let field = &bytes[offset..offset + size];
+        match endian {
+            Endian::Big => Self::from_be_bytes(field),
+            Endian::Little => Self::from_le_bytes(field),
+        }
+    }
+}
+
+macro_rules! from_bytes_tuple {
+    ($(($X: ident, $X0: tt)),*) => {
+        impl<$($X),*> FromBytesAt for ($($X,)*)
+        where
+            $($X: FromBytesAt,)*
+        {
+            type Layout = ($($X::Layout,)*);
+
+            #[inline]
+            fn from_bytes_at(bytes: &[u8], layout: Self::Layout) -> Self {
+                ($($X::from_bytes_at(bytes, layout.$X0),)*)
+            }
+        }
+    };
+}
+
+from_bytes_tuple!((A, 0));
+from_bytes_tuple!((A, 0), (B, 1));
+from_bytes_tuple!((A, 0), (B, 1), (C, 2));
+from_bytes_tuple!((A, 0), (B, 1), (C, 2), (D, 3));
+from_bytes_tuple!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4));
+from_bytes_tuple!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn header() {
+        // An IPv4-like header: version/ihl, tos, total length, id, flags/fragment, ttl, protocol,
+        // checksum, source and destination addresses.
+        let header = [
+            0x45, 0x00, 0x00, 0x54, 0xAB, 0xCD, 0x40, 0x00, 0x40, 0x01, 0x12, 0x34, 192, 168, 0, 1,
+            10, 0, 0, 2,
+        ];
+        let (words, addrs, first) = <(u16x4, u32x2, u8x2)>::from_bytes_at(
+            &header,
+            ((0, Endian::Big), (12, Endian::Big), (0, Endian::Little)),
+        );
+        assert_eq!(words, u16x4::new([0x4500, 0x0054, 0xABCD, 0x4000]));
+        assert_eq!(addrs, u32x2::new([0xC0A8_0001, 0x0A00_0002]));
+        assert_eq!(first, u8x2::new([0x45, 0x00]));
+
+        let le = u16x2::from_bytes_at(&header, (2, Endian::Little));
+        assert_eq!(le, u16x2::new([0x5400, 0xCDAB]));
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't fit")]
+    fn out_of_bounds() {
+        u32x2::from_bytes_at(&[0; 10], (4, Endian::Big));
+    }
+}
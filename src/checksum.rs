@@ -0,0 +1,155 @@
+//! Checksums computed with vectors.
+//!
+//! The algorithms here don't use lookup tables. Instead, the input is split into several
+//! independent parts, each processed in one lane of a vector, and the partial results are combined
+//! together at the end.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use slipstream::checksum;
+//!
+//! assert_eq!(checksum::crc32(b"123456789"), 0xCBF4_3926);
+//! ```
+
+use crate::types::{u32x16, u8x16};
+
+/// The (reflected) CRC-32 polynomial, as used by ethernet, zlib, PNG and others.
+const POLY: u32 = 0xEDB8_8320;
+
+/// Number of independent parts the input is split into.
+const LANES: usize = 16;
+
+// Feeds the data into the (already inverted) CRC register, one bit at a time.
+#[inline(always)]
+fn update(mut crc: u32, data: &[u8]) -> u32 {
+    for &b in data {
+        crc ^= u32::from(b);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLY
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
+
+// Carry-less multiplication of two polynomials modulo the CRC polynomial (in the reflected bit
+// order, so x^0 is the top bit).
+fn mul_mod(a: u32, mut b: u32) -> u32 {
+    let mut result = 0;
+    for i in (0..32).rev() {
+        if a & (1 << i) != 0 {
+            result ^= b;
+        }
+        b = if b & 1 == 1 { (b >> 1) ^ POLY } else { b >> 1 };
+    }
+    result
+}
+
+// x^(8 * len) modulo the CRC polynomial, by repeated squaring.
+fn shift_bytes(mut len: usize) -> u32 {
+    let mut result = 1 << 31; // x^0
+    let mut square = 1 << 23; // x^8
+    while len > 0 {
+        if len & 1 == 1 {
+            result = mul_mod(square, result);
+        }
+        len >>= 1;
+        square = mul_mod(square, square);
+    }
+    result
+}
+
+// CRC of two blocks one after another, given the CRCs of the blocks.
+fn combine(first: u32, second: u32, second_len: usize) -> u32 {
+    mul_mod(shift_bytes(second_len), first) ^ second
+}
+
+/// Computes the CRC-32 checksum of the data.
+///
+/// This is the common CRC-32 (sometimes called CRC-32/ISO-HDLC), used for example by zlib, gzip,
+/// PNG or ethernet.
+///
+/// The data is split into 16 parts of the same length, the CRCs of which are computed in the
+/// lanes of a [`u32x16`] (loading the bytes in [`u8x16`] chunks). These are then combined into the
+/// CRC of the whole, using multiplication of polynomials. The short leftover at the end is
+/// processed by scalar code.
+///
+/// The algorithm is table-free and processes one bit at a time in each lane. Don't expect it to
+/// beat dedicated CRC instructions of the CPU.
+///
+/// Like other functions in this library, it is marked `#[inline]`, so it can be used inside
+/// the `dispatch!` macro to take advantage of better instruction sets.
+///
+/// # Examples
+///
+/// ```rust
+/// use slipstream::checksum::crc32;
+///
+/// assert_eq!(crc32(b""), 0);
+/// assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414F_A339);
+/// ```
+#[inline]
+pub fn crc32(data: &[u8]) -> u32 {
+    let part = data.len() / LANES;
+    let mut crc = 0;
+    if part > 0 {
+        let mut idx = [0; LANES];
+        for (i, idx) in idx.iter_mut().enumerate() {
+            *idx = i * part;
+        }
+        let mut regs = u32x16::splat(!0);
+        for _ in 0..part {
+            let bytes = u8x16::gather_load(data, idx);
+            for (reg, &b) in regs.iter_mut().zip(bytes.iter()) {
+                *reg ^= u32::from(b);
+            }
+            for _ in 0..8 {
+                let odd = (regs & 1).eq(u32x16::splat(1));
+                let shifted = regs >> 1;
+                regs = shifted.blend(shifted ^ POLY, odd);
+            }
+            for idx in &mut idx {
+                *idx += 1;
+            }
+        }
+        for reg in regs.iter() {
+            crc = combine(crc, !reg, part);
+        }
+    }
+    !update(!crc, &data[part * LANES..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(data: &[u8]) -> u32 {
+        !update(!0, data)
+    }
+
+    #[test]
+    fn known() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"a"), 0xE8B7_BE43);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(
+            crc32(b"The quick brown fox jumps over the lazy dog"),
+            0x414F_A339
+        );
+        assert_eq!(crc32(&[0; 32]), 0x190A_55AD);
+    }
+
+    #[test]
+    fn lengths() {
+        let data = (0..1000u32)
+            .map(|i| (i * 7919 % 251) as u8)
+            .collect::<Vec<_>>();
+        for len in (0..100).chain([255, 256, 257, 1000].iter().copied()) {
+            assert_eq!(crc32(&data[..len]), reference(&data[..len]), "{}", len);
+        }
+    }
+}
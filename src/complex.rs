@@ -0,0 +1,266 @@
+//! Vectors of complex numbers.
+//!
+//! The [`Complex`] type wraps a float vector with even number of lanes and treats each pair of
+//! lanes as one complex number ‒ the real part first, the imaginary second (this is the same
+//! layout as slices of complex numbers usually have in memory, so the data can be loaded directly
+//! from them). A [`f32x8`][crate::f32x8] therefore holds 4 complex numbers.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use slipstream::prelude::*;
+//! use slipstream::complex::Complex;
+//!
+//! // (1 + 2i) and (3 - 1i)
+//! let a = Complex::new(f32x4::new([1.0, 2.0, 3.0, -1.0]));
+//! // i and 2
+//! let b = Complex::new(f32x4::new([0.0, 1.0, 2.0, 0.0]));
+//! assert_eq!((a * b).into_inner(), f32x4::new([-2.0, 1.0, 6.0, -2.0]));
+//! ```
+
+use core::ops::*;
+
+use num_traits::Float;
+
+use crate::inner::Repr;
+use crate::vector::align::Align;
+use crate::vector::DivisibleCheck;
+use crate::Vector;
+
+/// A vector of complex numbers, stored as interleaved real and imaginary parts.
+///
+/// See the [module documentation][crate::complex].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Complex<V>(V);
+
+impl<A, B, const S: usize> Complex<Vector<A, B, S>>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    // Mask selecting the real parts (the even lanes).
+    #[inline(always)]
+    fn real_mask() -> [bool; S] {
+        let mut mask = [false; S];
+        for (i, m) in mask.iter_mut().enumerate() {
+            *m = i % 2 == 0;
+        }
+        mask
+    }
+
+    // Moves the lanes to the other part of the same complex number ([re, im] -> [im, re]).
+    #[inline(always)]
+    fn swap_parts(v: Vector<A, B, S>) -> Vector<A, B, S> {
+        let mut idx = [0; S];
+        for (i, idx) in idx.iter_mut().enumerate() {
+            *idx = i ^ 1;
+        }
+        Vector::gather_load(v, idx)
+    }
+
+    // Copies one part over both lanes of each complex number.
+    #[inline(always)]
+    fn duplicate(v: Vector<A, B, S>, part: usize) -> Vector<A, B, S> {
+        let mut idx = [0; S];
+        for (i, idx) in idx.iter_mut().enumerate() {
+            *idx = i & !1 | part;
+        }
+        Vector::gather_load(v, idx)
+    }
+
+    /// Wraps a vector of interleaved real and imaginary parts.
+    ///
+    /// The vector must have even number of lanes (checked at compile time).
+    #[inline]
+    pub fn new(interleaved: Vector<A, B, S>) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = DivisibleCheck::<S, 2>::DIVISIBLE;
+        Complex(interleaved)
+    }
+
+    /// Returns the underlying vector of interleaved real and imaginary parts.
+    #[inline]
+    pub fn into_inner(self) -> Vector<A, B, S> {
+        self.0
+    }
+
+    /// Complex conjugate of each number (negates the imaginary parts).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use slipstream::complex::Complex;
+    ///
+    /// let c = Complex::new(f32x4::new([1.0, 2.0, 3.0, -4.0]));
+    /// assert_eq!(c.conj().into_inner(), f32x4::new([1.0, -2.0, 3.0, 4.0]));
+    /// ```
+    #[inline]
+    pub fn conj(self) -> Self {
+        Complex((-self.0).blend(self.0, Self::real_mask()))
+    }
+
+    /// The squared absolute value of each number.
+    ///
+    /// As the result is a real number, it is stored into both lanes of the corresponding complex
+    /// number.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use slipstream::complex::Complex;
+    ///
+    /// let c = Complex::new(f32x4::new([3.0, 4.0, 0.0, -2.0]));
+    /// assert_eq!(c.norm_sqr(), f32x4::new([25.0, 25.0, 4.0, 4.0]));
+    /// ```
+    #[inline]
+    pub fn norm_sqr(self) -> Vector<A, B, S> {
+        let sq = self.0 * self.0;
+        sq + Self::swap_parts(sq)
+    }
+}
+
+impl<A, B, const S: usize> Add for Complex<Vector<A, B, S>>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Complex(self.0 + rhs.0)
+    }
+}
+
+impl<A, B, const S: usize> Sub for Complex<Vector<A, B, S>>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Complex(self.0 - rhs.0)
+    }
+}
+
+impl<A, B, const S: usize> Neg for Complex<Vector<A, B, S>>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Complex(-self.0)
+    }
+}
+
+impl<A, B, const S: usize> Mul for Complex<Vector<A, B, S>>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    type Output = Self;
+    /// Multiplies the complex numbers, `(a + bi)(c + di) = (ac - bd) + (ad + bc)i`.
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        let re = Self::duplicate(self.0, 0); // [a, a]
+        let im = Self::duplicate(self.0, 1); // [b, b]
+        let swapped = Self::swap_parts(rhs.0); // [d, c]
+        let cross = im * swapped; // [bd, bc]
+        let cross = cross.blend(-cross, Self::real_mask()); // [-bd, bc]
+        Complex(re.mul_add(rhs.0, cross))
+    }
+}
+
+impl<A, B, const S: usize> AddAssign for Complex<Vector<A, B, S>>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<A, B, const S: usize> SubAssign for Complex<Vector<A, B, S>>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<A, B, const S: usize> MulAssign for Complex<Vector<A, B, S>>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_complex::Complex64;
+
+    use super::*;
+    use crate::prelude::*;
+
+    fn to_vec(c: &[Complex64]) -> Complex<f64x8> {
+        let lanes = c.iter().flat_map(|c| vec![c.re, c.im]).collect::<Vec<_>>();
+        Complex::new(f64x8::new(lanes))
+    }
+
+    fn check(got: Complex<f64x8>, expected: &[Complex64]) {
+        let expected = to_vec(expected).into_inner();
+        for (g, e) in got.into_inner().iter().zip(expected.iter()) {
+            assert!((g - e).abs() < 1e-12, "{} vs {}", g, e);
+        }
+    }
+
+    #[test]
+    fn ops() {
+        let a = [
+            Complex64::new(1.0, 2.0),
+            Complex64::new(-3.5, 0.25),
+            Complex64::new(0.0, -1.0),
+            Complex64::new(7.0, 0.0),
+        ];
+        let b = [
+            Complex64::new(0.5, -2.0),
+            Complex64::new(2.0, 2.0),
+            Complex64::new(0.0, -1.0),
+            Complex64::new(-1.0, 3.0),
+        ];
+        let (va, vb) = (to_vec(&a), to_vec(&b));
+
+        let prod = a.iter().zip(&b).map(|(a, b)| a * b).collect::<Vec<_>>();
+        check(va * vb, &prod);
+        let sum = a.iter().zip(&b).map(|(a, b)| a + b).collect::<Vec<_>>();
+        check(va + vb, &sum);
+        let diff = a.iter().zip(&b).map(|(a, b)| a - b).collect::<Vec<_>>();
+        check(va - vb, &diff);
+        let conj = a.iter().map(|a| a.conj()).collect::<Vec<_>>();
+        check(va.conj(), &conj);
+
+        let norms = a
+            .iter()
+            .flat_map(|a| vec![a.norm_sqr(); 2])
+            .collect::<Vec<_>>();
+        assert_eq!(va.norm_sqr(), f64x8::new(norms));
+
+        let mut acc = va;
+        acc *= vb;
+        acc += va;
+        acc -= vb;
+        let expected = (0..4)
+            .map(|i| a[i] * b[i] + a[i] - b[i])
+            .collect::<Vec<_>>();
+        check(acc, &expected);
+    }
+}
@@ -0,0 +1,360 @@
+//! Ready-made implementations of common computations.
+//!
+//! These are the well-known building blocks, written the way that tends to vectorize well. They
+//! can be used directly or as a reasonable baseline to compare a hand-tuned version against.
+//!
+//! Similar to the free-standing [`vectorize`][crate::vectorize], the functions need to be told
+//! what vector type to use for the computation, usually with a turbofish.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use slipstream::prelude::*;
+//! use slipstream::compute;
+//!
+//! let l = [1.0, 2.0, 3.0];
+//! let r = [4.0, 5.0, 6.0];
+//! assert_eq!(compute::dot::<f32x8, _>(&l, &r), 32.0);
+//! ```
+
+use core::ops::Add;
+
+use num_traits::MulAdd;
+
+use crate::inner::{Repr, VectorOf};
+use crate::iterators::MutProxy;
+use crate::vector::align::Align;
+use crate::{Mask, Vector, Vectorizable};
+
+/// Computes the dot product of two slices.
+///
+/// The slices are split into vectors of type `V` (the uneven end is padded with zeroes). The
+/// products are accumulated lane-wise using the fused multiply-add (for floats, this has only one
+/// rounding error) and the lanes are summed together only at the very end.
+///
+/// # Examples
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let l = (0..100).map(|i| i as f32).collect::<Vec<_>>();
+/// let r = vec![2.0; 100];
+/// assert_eq!(slipstream::compute::dot::<f32x8, _>(&l, &r), 9900.0);
+/// ```
+///
+/// # Panics
+///
+/// If the slices are of different lengths.
+#[inline]
+pub fn dot<V, B>(l: &[B], r: &[B]) -> B
+where
+    B: Repr + Default + Add<Output = B> + MulAdd<Output = B>,
+    V: VectorOf<B> + Default + AsRef<[B]> + AsMut<[B]>,
+    for<'a> &'a [B]: Vectorizable<V, Padding = V>,
+{
+    assert_eq!(
+        l.len(),
+        r.len(),
+        "Dot product of slices of different lengths"
+    );
+    (l, r)
+        .vectorize_pad((V::default(), V::default()))
+        .dot_reduce::<V, B>()
+}
+
+/// The dot product as a reduction of an iterator of vector pairs.
+///
+/// This is implemented for all iterators of pairs of vectors, like the ones produced by
+/// [`vectorize`][Vectorizable::vectorize] on a pair of slices. It is also part of the
+/// [`prelude`][crate::prelude].
+pub trait DotReduce: Iterator + Sized {
+    /// Computes the dot product of all the vector pairs.
+    ///
+    /// The products are accumulated lane-wise using the fused multiply-add and the lanes are
+    /// summed together only once at the very end. This is the same computation as in
+    /// [`dot`], but can be used with any vectorized iterator (for example with the padding
+    /// already applied or with the pairs prepared by a `map`).
+    ///
+    /// Similar to [`dot`], the vector type needs to be specified, usually with a turbofish.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = [1.0; 16];
+    /// let b = (0..16).map(|i| i as f32).collect::<Vec<_>>();
+    /// let dot = (&a[..], &b[..]).vectorize().dot_reduce::<f32x8, _>();
+    /// assert_eq!(dot, 120.0);
+    /// ```
+    #[inline]
+    fn dot_reduce<V, B>(self) -> B
+    where
+        Self: Iterator<Item = (V, V)>,
+        B: Repr + Default + Add<Output = B> + MulAdd<Output = B>,
+        V: VectorOf<B> + Default + AsRef<[B]> + AsMut<[B]>,
+    {
+        let mut result = V::default();
+        for (l, r) in self {
+            for ((res, l), r) in result.as_mut().iter_mut().zip(l.as_ref()).zip(r.as_ref()) {
+                *res = l.mul_add(*r, *res);
+            }
+        }
+        result.horizontal_sum()
+    }
+}
+
+impl<I: Iterator> DotReduce for I {}
+
+/// Multiplies two matrices.
+///
+/// The matrices are stored in row-major order. The `a` matrix has `rows` rows and `inner`
+/// columns, `b` has `inner` rows and `cols` columns. The result (of `rows` rows and `cols`
+/// columns) is stored into `out`.
+///
+/// The rows of the output are computed as a sum of the rows of `b` multiplied by the
+/// corresponding element of `a`, using the fused multiply-add. The rows are split into vectors of
+/// type `V`. This avoids the need to gather the columns of `b`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let a = [1, 2,
+///          3, 4];
+/// let b = [5, 6, 7,
+///          8, 9, 10];
+/// let mut out = [0; 6];
+/// slipstream::compute::matmul::<u32x4, _>(&a, &b, &mut out, 2, 2, 3);
+/// assert_eq!(out, [21, 24, 27,
+///                  47, 54, 61]);
+/// ```
+///
+/// # Panics
+///
+/// If the lengths of the slices don't match the dimensions.
+#[inline]
+pub fn matmul<V, B>(a: &[B], b: &[B], out: &mut [B], rows: usize, inner: usize, cols: usize)
+where
+    B: Repr + Default + MulAdd<Output = B>,
+    V: VectorOf<B> + Default + AsRef<[B]> + AsMut<[B]>,
+    for<'a> (&'a mut [B], &'a [B]): Vectorizable<(MutProxy<'a, B, V>, V), Padding = (V, V)>,
+{
+    assert_eq!(a.len(), rows * inner, "Wrong size of the left matrix");
+    assert_eq!(b.len(), inner * cols, "Wrong size of the right matrix");
+    assert_eq!(out.len(), rows * cols, "Wrong size of the output matrix");
+    if cols == 0 {
+        return;
+    }
+    for o in out.iter_mut() {
+        *o = B::default();
+    }
+    if inner == 0 {
+        return;
+    }
+    for (a_row, out_row) in a.chunks_exact(inner).zip(out.chunks_exact_mut(cols)) {
+        for (&a_ik, b_row) in a_row.iter().zip(b.chunks_exact(cols)) {
+            let pad = (V::default(), V::default());
+            for (mut o, b) in (&mut out_row[..], b_row).vectorize_pad(pad) {
+                for (o, b) in o.as_mut().iter_mut().zip(b.as_ref()) {
+                    *o = a_ik.mul_add(*b, *o);
+                }
+            }
+        }
+    }
+}
+
+/// Splits the data into two outputs according to a vectorized predicate.
+///
+/// The data are processed in vectors (the type is set by the parameter of the `predicate`). For
+/// each of them, the predicate produces a mask. The elements with the lane of the mask set are
+/// [compressed][Vector::compress] and written to the `passed` output, the other ones to the
+/// `failed` output. Both keep the original relative order. The leftover at the end that doesn't
+/// fill a whole vector is handed to the predicate padded by copies of its first element, but
+/// only the real elements are written.
+///
+/// Returns how many elements were written to `passed` and `failed` (these always sum up to the
+/// length of the data). The rest of the outputs is left untouched.
+///
+/// # Examples
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let data = (0..10).collect::<Vec<u32>>();
+/// let mut even = [0; 10];
+/// let mut odd = [0; 10];
+/// let (e, o) = slipstream::compute::partition(&data, &mut even, &mut odd, |v: u32x4| {
+///     (v & u32x4::splat(1)).eq(u32x4::splat(0))
+/// });
+/// assert_eq!(&even[..e], &[0, 2, 4, 6, 8]);
+/// assert_eq!(&odd[..o], &[1, 3, 5, 7, 9]);
+/// ```
+///
+/// # Panics
+///
+/// If any of the outputs is too short to hold the elements destined to it.
+#[inline]
+pub fn partition<A, B, P, M, MB, const S: usize>(
+    data: &[B],
+    passed: &mut [B],
+    failed: &mut [B],
+    mut predicate: P,
+) -> (usize, usize)
+where
+    A: Align,
+    B: Repr,
+    P: FnMut(Vector<A, B, S>) -> M,
+    M: AsRef<[MB]>,
+    MB: Mask,
+{
+    let mut cnt_passed = 0;
+    let mut cnt_failed = 0;
+    let mut process = |v: Vector<A, B, S>, valid: usize| {
+        let mask = predicate(v);
+        let mask = mask.as_ref();
+        assert_eq!(mask.len(), S, "Predicate produced wrong sized mask");
+        let mut pass = [false; S];
+        let mut fail = [false; S];
+        for i in 0..valid {
+            pass[i] = mask[i].bool();
+            fail[i] = !pass[i];
+        }
+        let (pv, pc) = v.compress(pass);
+        let (fv, fc) = v.compress(fail);
+        assert!(
+            cnt_passed + pc <= passed.len(),
+            "Output for passed elements too short"
+        );
+        assert!(
+            cnt_failed + fc <= failed.len(),
+            "Output for failed elements too short"
+        );
+        passed[cnt_passed..cnt_passed + pc].copy_from_slice(&pv[..pc]);
+        failed[cnt_failed..cnt_failed + fc].copy_from_slice(&fv[..fc]);
+        cnt_passed += pc;
+        cnt_failed += fc;
+    };
+    let chunks = data.chunks_exact(S);
+    let rest = chunks.remainder();
+    for chunk in chunks {
+        process(Vector::new(chunk), S);
+    }
+    if let Some(&first) = rest.first() {
+        let mut v = Vector::splat(first);
+        v[..rest.len()].copy_from_slice(rest);
+        process(v, rest.len());
+    }
+    (cnt_passed, cnt_failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn data(len: usize, seed: u32) -> Vec<f32> {
+        (0..len as u32)
+            .map(|i| ((i * 7 + seed) % 13) as f32 / 3.0 - 2.0)
+            .collect()
+    }
+
+    #[test]
+    fn dot_scalar() {
+        for len in [0, 1, 7, 8, 9, 100, 1001].iter().copied() {
+            let l = data(len, 1);
+            let r = data(len, 5);
+            // Reference computed with better precision
+            let expected: f64 = l.iter().zip(&r).map(|(&l, &r)| l as f64 * r as f64).sum();
+            let dot8 = dot::<f32x8, _>(&l, &r) as f64;
+            let dot4 = dot::<f32x4, _>(&l, &r) as f64;
+            let tolerance = 1e-5 * expected.abs().max(1.0);
+            assert!(
+                (expected - dot8).abs() < tolerance,
+                "{} vs {}",
+                expected,
+                dot8
+            );
+            assert!(
+                (expected - dot4).abs() < tolerance,
+                "{} vs {}",
+                expected,
+                dot4
+            );
+        }
+    }
+
+    #[test]
+    fn dot_reduce() {
+        let l = data(64, 2);
+        let r = data(64, 9);
+        let expected: f64 = l.iter().zip(&r).map(|(&l, &r)| l as f64 * r as f64).sum();
+        let got = (&l[..], &r[..]).vectorize().dot_reduce::<f32x8, _>() as f64;
+        assert!((expected - got).abs() < 1e-5 * expected.abs().max(1.0));
+
+        let doubled = (&l[..], &r[..])
+            .vectorize()
+            .map(|(l, r): (f32x4, f32x4)| (l * f32x4::splat(2.0), r))
+            .dot_reduce::<f32x4, _>() as f64;
+        assert!((2.0 * expected - doubled).abs() < 1e-5 * expected.abs().max(1.0));
+    }
+
+    #[test]
+    fn dot_int() {
+        let l = (0..17).collect::<Vec<u32>>();
+        assert_eq!(dot::<u32x4, _>(&l, &l), l.iter().map(|i| i * i).sum());
+    }
+
+    #[test]
+    #[should_panic(expected = "Dot product of slices of different lengths")]
+    fn dot_lengths() {
+        dot::<f32x4, _>(&[1.0, 2.0], &[1.0]);
+    }
+
+    #[test]
+    fn partition_even_odd() {
+        for len in [0, 1, 3, 8, 9, 100].iter().copied() {
+            let data = (0..len).map(|i| i * 7 % 11).collect::<Vec<u32>>();
+            let mut even = vec![u32::MAX; len as usize + 1];
+            let mut odd = vec![u32::MAX; len as usize];
+            let (e, o) = partition(&data, &mut even, &mut odd, |v: u32x8| {
+                (v % u32x8::splat(2)).is_zero()
+            });
+            let expected_even = data
+                .iter()
+                .copied()
+                .filter(|i| i % 2 == 0)
+                .collect::<Vec<_>>();
+            let expected_odd = data
+                .iter()
+                .copied()
+                .filter(|i| i % 2 == 1)
+                .collect::<Vec<_>>();
+            assert_eq!(&even[..e], &expected_even[..]);
+            assert_eq!(&odd[..o], &expected_odd[..]);
+            assert_eq!(even[e], u32::MAX);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Output for failed elements too short")]
+    fn partition_short() {
+        let data = [1, 2, 3, 4, 5];
+        partition(&data, &mut [0; 5], &mut [0; 2], |v: u32x4| {
+            v.gt(u32x4::splat(3))
+        });
+    }
+
+    #[test]
+    fn matmul_scalar() {
+        let (rows, inner, cols) = (5, 7, 11);
+        let a = data(rows * inner, 3);
+        let b = data(inner * cols, 4);
+        let mut out = vec![42.0; rows * cols];
+        matmul::<f32x4, _>(&a, &b, &mut out, rows, inner, cols);
+        for r in 0..rows {
+            for c in 0..cols {
+                let expected: f32 = (0..inner).map(|i| a[r * inner + i] * b[i * cols + c]).sum();
+                let got = out[r * cols + c];
+                assert!((expected - got).abs() < 1e-3, "{} vs {}", expected, got);
+            }
+        }
+    }
+}
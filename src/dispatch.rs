@@ -0,0 +1,238 @@
+//! Lightweight runtime selection of the instruction set.
+//!
+//! By default, the compiler generates code for a conservative baseline CPU (eg. only SSE2 on
+//! `x86_64`). To take advantage of newer instructions and still run on older CPUs, the function
+//! needs to be compiled multiple times and the right version picked at runtime, depending on what
+//! the CPU actually supports.
+//!
+//! The [`multiversion`] crate does that in a very flexible way. This module provides a much
+//! simpler helper for the common case, without the need for another dependency:
+//!
+//! * The [`dispatch!`][crate::dispatch!] macro compiles a function for several instruction sets
+//!   and calls the best one available on the current CPU.
+//! * The [`best_instruction_set`] function can be used to make the decision manually (for example
+//!   to pick the width of the vectors).
+//!
+//! This needs the `std` feature, because the detection of the CPU features is available only in
+//! the standard library.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use slipstream::prelude::*;
+//! slipstream::dispatch! {
+//!     fn sum(data: &[f32]) -> f32 {
+//!         data.vectorize_pad(f32x8::default())
+//!             .sum::<f32x8>()
+//!             .horizontal_sum()
+//!     }
+//! }
+//!
+//! assert_eq!(sum(&[1.0, 2.0, 3.0]), 6.0);
+//! ```
+//!
+//! [`multiversion`]: https://crates.io/crates/multiversion
+
+/// An instruction set family the code can be compiled for.
+///
+/// The variants are ordered from the least to the most capable, but only the ones on the same
+/// architecture are comparable in a meaningful way.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum InstructionSet {
+    /// Whatever the compiler targets by default.
+    Baseline,
+    /// The SSE family up to `SSE4.1` (`x86` and `x86_64`).
+    Sse41,
+    /// The `AVX` instructions (`x86` and `x86_64`).
+    Avx,
+    /// The `AVX2` and `FMA` instructions (`x86` and `x86_64`).
+    Avx2,
+    /// The `NEON` instructions (`aarch64`).
+    Neon,
+}
+
+impl InstructionSet {
+    /// Width of the SIMD registers of this instruction set, in bytes.
+    ///
+    /// This is a hint for choosing the width of the vectors to use ‒ vectors at least this wide
+    /// fill the whole register.
+    ///
+    /// The [`Baseline`][InstructionSet::Baseline] is considered to have 16-byte registers, as
+    /// that's what most of the targets support.
+    ///
+    /// ```rust
+    /// # use slipstream::dispatch::InstructionSet;
+    /// assert_eq!(InstructionSet::Avx2.vector_bytes(), 32);
+    /// ```
+    pub fn vector_bytes(self) -> usize {
+        match self {
+            InstructionSet::Avx | InstructionSet::Avx2 => 32,
+            _ => 16,
+        }
+    }
+}
+
+/// Detects the most capable instruction set supported by the current CPU.
+///
+/// The detection is cached by the standard library, so it is cheap to call repeatedly.
+///
+/// # Examples
+///
+/// ```rust
+/// use slipstream::dispatch::best_instruction_set;
+/// use slipstream::prelude::*;
+///
+/// let data = [1.0; 100];
+/// // Use vectors wide enough to fill the registers.
+/// let total = if best_instruction_set().vector_bytes() >= 32 {
+///     data.vectorize_pad(f32x8::default()).sum::<f32x8>().horizontal_sum()
+/// } else {
+///     data.vectorize_pad(f32x4::default()).sum::<f32x4>().horizontal_sum()
+/// };
+/// assert_eq!(total, 100.0);
+/// ```
+pub fn best_instruction_set() -> InstructionSet {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx2") && std::is_x86_feature_detected!("fma") {
+            return InstructionSet::Avx2;
+        }
+        if std::is_x86_feature_detected!("avx") {
+            return InstructionSet::Avx;
+        }
+        if std::is_x86_feature_detected!("sse4.1") {
+            return InstructionSet::Sse41;
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return InstructionSet::Neon;
+        }
+    }
+    InstructionSet::Baseline
+}
+
+/// Compiles a function for multiple instruction sets and picks the best one at runtime.
+///
+/// The macro takes a function definition. The body is compiled once for each of the supported
+/// [instruction sets][crate::dispatch::InstructionSet] of the target architecture and a call to
+/// the function picks the best variant supported by the current CPU (as detected by
+/// [`best_instruction_set`][crate::dispatch::best_instruction_set]).
+///
+/// The body is inlined into each variant, but the functions called from it are not compiled
+/// separately unless they get inlined too. Therefore, mark the helper functions with
+/// `#[inline]`.
+///
+/// Only simple non-generic functions are supported (no generics, no `self`, the arguments must be
+/// plain identifiers).
+///
+/// # Examples
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// slipstream::dispatch! {
+///     /// Computes the dot product of two equally long slices.
+///     pub fn dot_product(l: &[f32], r: &[f32]) -> f32 {
+///         (l, r)
+///             .vectorize_pad((f32x8::default(), f32x8::default()))
+///             .map(|(l, r)| l * r)
+///             .sum::<f32x8>()
+///             .horizontal_sum()
+///     }
+/// }
+///
+/// assert_eq!(dot_product(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]), 32.0);
+/// ```
+#[macro_export]
+macro_rules! dispatch {
+    ($(#[$meta: meta])* $vis: vis fn $name: ident($($arg: ident: $ty: ty),* $(,)?)
+        $(-> $ret: ty)? $body: block
+    ) => {
+        $(#[$meta])*
+        $vis fn $name($($arg: $ty),*) $(-> $ret)? {
+            #[inline(always)]
+            fn imp($($arg: $ty),*) $(-> $ret)? $body
+
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            #[target_feature(enable = "sse2,sse3,ssse3,sse4.1,avx,avx2,fma")]
+            unsafe fn avx2($($arg: $ty),*) $(-> $ret)? {
+                imp($($arg),*)
+            }
+
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            #[target_feature(enable = "sse2,sse3,ssse3,sse4.1,avx")]
+            unsafe fn avx($($arg: $ty),*) $(-> $ret)? {
+                imp($($arg),*)
+            }
+
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            #[target_feature(enable = "sse2,sse3,ssse3,sse4.1")]
+            unsafe fn sse41($($arg: $ty),*) $(-> $ret)? {
+                imp($($arg),*)
+            }
+
+            #[cfg(target_arch = "aarch64")]
+            #[target_feature(enable = "neon")]
+            unsafe fn neon($($arg: $ty),*) $(-> $ret)? {
+                imp($($arg),*)
+            }
+
+            #[allow(unreachable_patterns)]
+            match $crate::dispatch::best_instruction_set() {
+                // Safety: the CPU features were detected to be present.
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                $crate::dispatch::InstructionSet::Avx2 => unsafe { avx2($($arg),*) },
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                $crate::dispatch::InstructionSet::Avx => unsafe { avx($($arg),*) },
+                #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+                $crate::dispatch::InstructionSet::Sse41 => unsafe { sse41($($arg),*) },
+                #[cfg(target_arch = "aarch64")]
+                $crate::dispatch::InstructionSet::Neon => unsafe { neon($($arg),*) },
+                _ => imp($($arg),*),
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    crate::dispatch! {
+        fn dot(l: &[f32], r: &[f32]) -> f32 {
+            (l, r)
+                .vectorize_pad((f32x8::default(), f32x8::default()))
+                .map(|(l, r)| l * r)
+                .sum::<f32x8>()
+                .horizontal_sum()
+        }
+    }
+
+    crate::dispatch! {
+        fn double(data: &mut [u32]) {
+            for mut v in data.vectorize_pad(u32x4::default()) {
+                *v *= 2;
+            }
+        }
+    }
+
+    #[test]
+    fn dispatched() {
+        let l = (0..100).map(|i| i as f32).collect::<Vec<_>>();
+        let r = (0..100).map(|i| (i % 7) as f32).collect::<Vec<_>>();
+        let expected: f32 = l.iter().zip(&r).map(|(l, r)| l * r).sum();
+        assert_eq!(dot(&l, &r), expected);
+
+        let mut data = [1, 2, 3, 4, 5];
+        double(&mut data);
+        assert_eq!(data, [2, 4, 6, 8, 10]);
+    }
+
+    #[test]
+    fn detected() {
+        let detected = best_instruction_set();
+        assert!(detected.vector_bytes() >= 16);
+    }
+}
@@ -0,0 +1,258 @@
+//! Vectors of fixed-point numbers.
+//!
+//! The [`FixedVector`] wraps an integer vector and interprets each lane as a fixed-point number
+//! with `FRAC` fractional bits (the `Qm.n` format, with `n = FRAC`). That is, the lane value `x`
+//! represents the number `x / 2^FRAC`. This is useful for DSP-style computations on platforms
+//! without fast floating point arithmetic, or where the determinism of integers is desirable.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use slipstream::prelude::*;
+//! use slipstream::fixed::FixedVector;
+//!
+//! // Q8.8 numbers in 16-bit lanes
+//! type Q8_8 = FixedVector<i16x4, 8>;
+//!
+//! let a = Q8_8::from_float(f32x4::new([1.5, -2.0, 0.25, 3.0]));
+//! let b = Q8_8::from_float(f32x4::splat(0.5));
+//! let product: f32x4 = (a * b).to_float();
+//! assert_eq!(product, f32x4::new([0.75, -1.0, 0.125, 1.5]));
+//! assert_eq!((a + b).to_bits(), i16x4::new([512, -384, 192, 896]));
+//! ```
+
+use core::mem;
+use core::ops::*;
+
+use num_traits::{Float, PrimInt};
+
+use crate::inner::{Repr, WideMul};
+use crate::vector::align::Align;
+use crate::Vector;
+
+// Compile time check the fractional bits fit into the base type.
+struct FracCheck<B, const FRAC: u32>(B);
+
+impl<B, const FRAC: u32> FracCheck<B, FRAC> {
+    const FITS: () = assert!(
+        FRAC as usize <= mem::size_of::<B>() * 8,
+        "More fractional bits than the type has"
+    );
+}
+
+/// A vector of fixed-point numbers with `FRAC` fractional bits.
+///
+/// The addition and subtraction are the same as of the underlying integers (including the
+/// behaviour on overflow). The multiplication computes the full product in a type of double width,
+/// shifts it right by `FRAC` bits (rounding to the nearest value) and truncates it back to the
+/// lane type. If the result doesn't fit, it wraps around.
+///
+/// See the [module documentation][crate::fixed].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FixedVector<V, const FRAC: u32>(V);
+
+impl<A, B, const S: usize, const FRAC: u32> FixedVector<Vector<A, B, S>, FRAC>
+where
+    A: Align,
+    B: WideMul + PrimInt,
+{
+    /// Wraps a vector of the raw representation.
+    ///
+    /// Each lane `x` represents the number `x / 2^FRAC`. The number of fractional bits must not be
+    /// bigger than the number of bits of the lane type (checked at compile time).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use slipstream::fixed::FixedVector;
+    ///
+    /// let half = FixedVector::<u8x2, 4>::from_bits(u8x2::splat(8));
+    /// let float: f32x2 = half.to_float();
+    /// assert_eq!(float, f32x2::splat(0.5));
+    /// ```
+    #[inline]
+    pub fn from_bits(bits: Vector<A, B, S>) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = FracCheck::<B, FRAC>::FITS;
+        FixedVector(bits)
+    }
+
+    /// Returns the raw representation.
+    #[inline]
+    pub fn to_bits(self) -> Vector<A, B, S> {
+        self.0
+    }
+
+    /// Converts from floating point numbers, rounding to the nearest representable value.
+    ///
+    /// Values out of the representable range saturate to the minimum or maximum, `NaN`s become
+    /// zero.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use slipstream::fixed::FixedVector;
+    ///
+    /// let v = FixedVector::<i8x4, 4>::from_float(f32x4::new([0.25, -1.0, 100.0, f32::NAN]));
+    /// assert_eq!(v.to_bits(), i8x4::new([4, -16, 127, 0]));
+    /// ```
+    #[inline]
+    pub fn from_float<A2, F>(value: Vector<A2, F, S>) -> Self
+    where
+        A2: Align,
+        F: Repr + Float,
+    {
+        let scale = (F::one() + F::one()).powi(FRAC as i32);
+        let mut bits = Vector::splat(B::zero());
+        for (b, v) in bits.iter_mut().zip(value.iter()) {
+            let scaled = (*v * scale).round();
+            *b = B::from(scaled).unwrap_or_else(|| {
+                if scaled.is_nan() {
+                    B::zero()
+                } else if scaled > F::zero() {
+                    B::max_value()
+                } else {
+                    B::min_value()
+                }
+            });
+        }
+        Self::from_bits(bits)
+    }
+
+    /// Converts to floating point numbers.
+    ///
+    /// The conversion is exact if the float type has enough bits of precision. The result type
+    /// usually needs to be specified.
+    #[inline]
+    pub fn to_float<A2, F>(self) -> Vector<A2, F, S>
+    where
+        A2: Align,
+        F: Repr + Float,
+    {
+        let scale = (F::one() + F::one()).powi(FRAC as i32);
+        let mut result = Vector::splat(F::zero());
+        for (r, b) in result.iter_mut().zip(self.0.iter()) {
+            *r = F::from(*b).unwrap() / scale;
+        }
+        result
+    }
+}
+
+impl<A, B, const S: usize, const FRAC: u32> Add for FixedVector<Vector<A, B, S>, FRAC>
+where
+    A: Align,
+    B: WideMul + PrimInt,
+{
+    type Output = Self;
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        FixedVector(self.0 + rhs.0)
+    }
+}
+
+impl<A, B, const S: usize, const FRAC: u32> Sub for FixedVector<Vector<A, B, S>, FRAC>
+where
+    A: Align,
+    B: WideMul + PrimInt,
+{
+    type Output = Self;
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        FixedVector(self.0 - rhs.0)
+    }
+}
+
+impl<A, B, const S: usize, const FRAC: u32> Mul for FixedVector<Vector<A, B, S>, FRAC>
+where
+    A: Align,
+    B: WideMul + PrimInt,
+{
+    type Output = Self;
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        let mut result = self.0;
+        for (res, r) in result.iter_mut().zip(rhs.0.iter()) {
+            *res = res.mul_shr(*r, FRAC);
+        }
+        FixedVector(result)
+    }
+}
+
+impl<A, B, const S: usize, const FRAC: u32> AddAssign for FixedVector<Vector<A, B, S>, FRAC>
+where
+    A: Align,
+    B: WideMul + PrimInt,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<A, B, const S: usize, const FRAC: u32> SubAssign for FixedVector<Vector<A, B, S>, FRAC>
+where
+    A: Align,
+    B: WideMul + PrimInt,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<A, B, const S: usize, const FRAC: u32> MulAssign for FixedVector<Vector<A, B, S>, FRAC>
+where
+    A: Align,
+    B: WideMul + PrimInt,
+{
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    type Q16 = FixedVector<i32x4, 16>;
+
+    #[test]
+    fn round_trip() {
+        let values = f64x4::new([0.0, -1.5, 3.140625, -32768.0]);
+        let fixed = Q16::from_float(values);
+        assert_eq!(fixed.to_bits(), i32x4::new([0, -98304, 205824, i32::MIN]));
+        let back: f64x4 = fixed.to_float();
+        assert_eq!(back, values);
+        assert_eq!(Q16::from_bits(fixed.to_bits()), fixed);
+
+        // Unsigned type with all bits fractional
+        let frac = FixedVector::<u8x4, 8>::from_float(f32x4::new([0.0, 0.5, 0.99, 2.0]));
+        assert_eq!(frac.to_bits(), u8x4::new([0, 128, 253, 255]));
+    }
+
+    #[test]
+    fn arith() {
+        let a = [1.25, -3.0, 0.001, 100.5];
+        let b = [-0.75, -2.5, 1000.0, 0.125];
+        let (fa, fb) = (
+            Q16::from_float(f64x4::new(a)),
+            Q16::from_float(f64x4::new(b)),
+        );
+        let prod: f64x4 = (fa * fb).to_float();
+        let sum: f64x4 = (fa + fb).to_float();
+        let diff: f64x4 = (fa - fb).to_float();
+        for i in 0..4 {
+            // Each input is rounded to 2^-17 at most, plus the rounding of the product
+            let tolerance = a[i].abs().max(b[i].abs()) * 2.0 / 65536.0 + 1.0 / 65536.0;
+            assert!((prod[i] - a[i] * b[i]).abs() <= tolerance, "{}", i);
+            assert!((sum[i] - (a[i] + b[i])).abs() <= 1.0 / 65536.0);
+            assert!((diff[i] - (a[i] - b[i])).abs() <= 1.0 / 65536.0);
+        }
+
+        let mut acc = fa;
+        acc *= fb;
+        acc += fa;
+        acc -= fb;
+        assert_eq!(acc, fa * fb + fa - fb);
+    }
+}
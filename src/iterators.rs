@@ -20,7 +20,7 @@
 //! # double(&[], &mut [])
 //! ```
 
-use core::iter::FusedIterator;
+use core::iter::{self, FusedIterator};
 use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
 use core::ops::*;
@@ -40,7 +40,8 @@ use crate::Vector;
 ///
 /// Note that the data are written in the destructor. Usually, this should not matter, but if you
 /// [`forget`][mem::forget], the changes will be lost (this is meant as a warning, not as a way to
-/// implement poor-man's transactions).
+/// implement poor-man's transactions). If the exact moment of the write back matters, use
+/// [`commit`][MutProxy::commit].
 #[derive(Debug)]
 pub struct MutProxy<'a, B, V>
 where
@@ -49,6 +50,74 @@ where
 {
     data: V,
     restore: &'a mut [B],
+    committed: bool,
+}
+
+impl<B, V> MutProxy<'_, B, V>
+where
+    V: AsRef<[B]>,
+    B: Copy,
+{
+    /// Writes the data back into the original slice right away.
+    ///
+    /// This consumes the proxy, therefore the borrow of the original slice ends here. It is
+    /// equivalent to dropping the proxy, but makes the point of the write back explicit in the
+    /// code.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use slipstream::iterators::MutProxy;
+    ///
+    /// let mut data = [1, 2, 3, 4];
+    /// let mut proxy: MutProxy<_, u32x4> = (&mut data[..]).vectorize().next().unwrap();
+    /// *proxy += u32x4::splat(1);
+    /// proxy.commit();
+    /// assert_eq!(data, [2, 3, 4, 5]);
+    /// ```
+    #[inline]
+    pub fn commit(mut self) {
+        self.write_back();
+        // The destructor runs right after this, but has nothing more to do.
+        self.committed = true;
+    }
+
+    #[inline]
+    fn write_back(&mut self) {
+        self.restore
+            .copy_from_slice(&self.data.as_ref()[..self.restore.len()]);
+    }
+}
+
+impl<B, V> MutProxy<'_, B, V>
+where
+    V: AsRef<[B]> + AsMut<[B]>,
+    B: Copy,
+{
+    /// Provides access to the lanes of the staged vector as a mutable slice.
+    ///
+    /// Just like modifications through the dereferenced vector, the changes are written back into
+    /// the original slice when the proxy is dropped. In case of the padding vector produced by
+    /// [`vectorize_pad`][Vectorizable::vectorize_pad], the lanes past the end of the original
+    /// slice are not written anywhere.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use slipstream::iterators::MutProxy;
+    ///
+    /// let mut data = [1, 2, 3, 4];
+    /// for mut v in slipstream::vectorize::<MutProxy<_, u32x2>, _>(&mut data[..]) {
+    ///     v.as_mut_slice()[1] = 0;
+    /// }
+    /// assert_eq!(data, [1, 0, 3, 0]);
+    /// ```
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [B] {
+        self.data.as_mut()
+    }
 }
 
 impl<B, V> Deref for MutProxy<'_, B, V>
@@ -81,8 +150,9 @@ where
 {
     #[inline]
     fn drop(&mut self) {
-        self.restore
-            .copy_from_slice(&self.data.as_ref()[..self.restore.len()]);
+        if !self.committed {
+            self.write_back();
+        }
     }
 }
 
@@ -140,6 +210,39 @@ pub struct VectorizedIter<V, P, R> {
     _result: PhantomData<R>,
 }
 
+impl<'a, A, B, P, const S: usize> VectorizedIter<ReadVectorizer<'a, A, B, S>, P, Vector<A, B, S>>
+where
+    A: Align,
+    B: Repr,
+{
+    /// Returns the uneven end of the slice that doesn't fill a whole vector.
+    ///
+    /// This is similar to [`ChunksExact::remainder`][slice::ChunksExact::remainder]. In case of
+    /// [`vectorize_pad`][Vectorizable::vectorize_pad], these are the elements that get into the
+    /// padded vector (which is produced last). Therefore, it is possible to process only the full
+    /// vectors in the main loop and handle the tail separately. In case of
+    /// [`vectorize`][Vectorizable::vectorize], the remainder is always empty (non-divisible input
+    /// is rejected up front).
+    ///
+    /// The remainder doesn't change during the iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let data = [1, 2, 3, 4, 5, 6, 7];
+    /// let iter = data.vectorize_pad(u32x2::default());
+    /// let rest = iter.remainder();
+    /// let full = iter.take(data.len() / 2).sum::<u32x2>();
+    /// assert_eq!(full, u32x2::new([9, 12]));
+    /// assert_eq!(rest, [7]);
+    /// ```
+    #[inline]
+    pub fn remainder(&self) -> &'a [B] {
+        self.vectorizer.rest
+    }
+}
+
 impl<V, P, R> Iterator for VectorizedIter<V, P, R>
 where
     V: Vectorizer<R>,
@@ -176,7 +279,6 @@ where
         self.next_back()
     }
 
-    // TODO: This wants some tests
     #[inline]
     fn nth(&mut self, n: usize) -> Option<R> {
         let main_len = self.right - self.left;
@@ -196,7 +298,6 @@ where
     V: Vectorizer<R>,
     P: Partial<R>,
 {
-    // TODO: Tests
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
         if let Some(partial) = self.partial.take_partial() {
@@ -208,6 +309,26 @@ where
             None
         }
     }
+
+    #[inline]
+    fn nth_back(&mut self, mut n: usize) -> Option<Self::Item> {
+        // The partial one is the last, so it goes first from the back
+        if self.partial.size() > 0 {
+            let partial = self.partial.take_partial();
+            if n == 0 {
+                return partial;
+            }
+            n -= 1;
+        }
+        let main_len = self.right - self.left;
+        if main_len > n {
+            self.right -= n;
+            self.next_back()
+        } else {
+            self.right = self.left;
+            None
+        }
+    }
 }
 
 impl<V, P, R> ExactSizeIterator for VectorizedIter<V, P, R>
@@ -227,7 +348,7 @@ where
 /// A trait describing things with direct support for splitting into vectors.
 ///
 /// This supports vectorized iteration over shared and mutable slices as well as types composed of
-/// them (tuples and short fixed-sized arrays).
+/// them (tuples and fixed-sized arrays of any length).
 ///
 /// Note that, unlike normal iterators, shared slices return owned values (vectors) and mutable
 /// slices return [proxy objects][MutProxy] that allow writing the data back. It is not possible to
@@ -349,12 +470,15 @@ pub trait Vectorizable<V>: Sized {
     ///
     /// The padding is produced at the end.
     ///
-    /// In case of composites, this still assumes they produce the same number of full vectors and
-    /// that they all either do or don't need a padding.
+    /// In case of composites, this still assumes they produce the same number of vectors (counting
+    /// the padding one) and that all the members that can be padded either do or don't need a
+    /// padding. In tuples, members with nothing to pad (their padding type is `()`, for example
+    /// already vectorized slices) are exempt ‒ their last vector is produced together with the
+    /// paddings of the others.
     ///
     /// # Panics
     ///
-    /// If the above assumption about number of vectors and same padding behaviour is violated.
+    /// If the above assumption about number of vectors and padding behaviour is violated.
     ///
     /// ```rust
     /// # use slipstream::prelude::*;
@@ -379,8 +503,8 @@ pub trait Vectorizable<V>: Sized {
 #[derive(Copy, Clone, Debug)]
 pub struct ReadVectorizer<'a, A: Align, B: Repr, const S: usize> {
     start: *const B,
+    rest: &'a [B],
     _vector: PhantomData<Vector<A, B, S>>,
-    _slice: PhantomData<&'a [B]>, // To hold the lifetime
 }
 
 // Note: The impls here assume V, B, P are Sync and Send, which they are. Nobody is able to create
@@ -407,7 +531,7 @@ impl<'a, A: Align, B: Repr, const S: usize> Vectorizable<Vector<A, B, S>> for &'
     ) -> (Self::Vectorizer, usize, Option<Vector<A, B, S>>) {
         let len = self.len();
         assert!(
-            len * mem::size_of::<B>() <= isize::MAX as usize,
+            mem::size_of_val(self) <= isize::MAX as usize,
             "Slice too huge"
         );
         let rest = len % S;
@@ -426,8 +550,8 @@ impl<'a, A: Align, B: Repr, const S: usize> Vectorizable<Vector<A, B, S>> for &'
         };
         let me = ReadVectorizer {
             start,
+            rest: &self[main..],
             _vector: PhantomData,
-            _slice: PhantomData,
         };
         (me, main / S, partial)
     }
@@ -458,6 +582,7 @@ impl<'a, A: Align, B: Repr, const S: usize> Vectorizer<MutProxy<'a, B, Vector<A,
         MutProxy {
             data: Vector::new_unchecked(ptr),
             restore: slice::from_raw_parts_mut(ptr, S),
+            committed: false,
         }
     }
 }
@@ -479,7 +604,7 @@ impl<'a, A: Align, B: Repr, const S: usize> Vectorizable<MutProxy<'a, B, Vector<
     ) {
         let len = self.len();
         assert!(
-            len * mem::size_of::<B>() <= isize::MAX as usize,
+            mem::size_of_val(self) <= isize::MAX as usize,
             "Slice too huge"
         );
         let rest = len % S;
@@ -490,7 +615,11 @@ impl<'a, A: Align, B: Repr, const S: usize> Vectorizable<MutProxy<'a, B, Vector<
             (_, Some(mut pad)) => {
                 let restore = &mut self[main..];
                 pad[..rest].copy_from_slice(restore);
-                Some(MutProxy { data: pad, restore })
+                Some(MutProxy {
+                    data: pad,
+                    restore,
+                    committed: false,
+                })
             }
             _ => panic!(
                 "Data to vectorize not divisible by lanes ({} vs {})",
@@ -506,6 +635,122 @@ impl<'a, A: Align, B: Repr, const S: usize> Vectorizable<MutProxy<'a, B, Vector<
     }
 }
 
+/// A write-only proxy for vectorizing uninitialized slices.
+///
+/// This is produced when vectorizing `&mut [MaybeUninit<B>]`, for example the spare capacity of a
+/// `Vec`. As the original data are not initialized, they can't be read and the
+/// proxy only allows [`write`][UninitProxy::write]ing a whole vector.
+///
+/// Writing is what initializes the lanes. If a proxy is dropped without writing, the
+/// corresponding part of the slice stays uninitialized. It is the caller's responsibility to make
+/// sure all the lanes were written before assuming they are initialized.
+///
+/// # Examples
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let input = (0..10).collect::<Vec<u32>>();
+/// let mut output = Vec::<u32>::with_capacity(input.len());
+/// let uninit = &mut output.spare_capacity_mut()[..input.len()];
+/// for (i, o) in (&input[..], uninit).vectorize_pad((u32x4::default(), ())) {
+///     o.write(i * u32x4::splat(2));
+/// }
+/// // Safety: All the lanes were written above.
+/// unsafe { output.set_len(input.len()) };
+/// assert_eq!(output, (0..10).map(|i| i * 2).collect::<Vec<_>>());
+/// ```
+#[derive(Debug)]
+pub struct UninitProxy<'a, B, V> {
+    target: &'a mut [MaybeUninit<B>],
+    _vector: PhantomData<V>,
+}
+
+impl<B, V> UninitProxy<'_, B, V>
+where
+    V: AsRef<[B]>,
+    B: Copy,
+{
+    /// Writes the vector into the slice, initializing the corresponding lanes.
+    ///
+    /// In case of the padding proxy, the lanes past the end of the slice are thrown away.
+    #[inline]
+    pub fn write(self, value: V) {
+        for (t, &v) in self.target.iter_mut().zip(value.as_ref()) {
+            *t = MaybeUninit::new(v);
+        }
+    }
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug)]
+pub struct UninitVectorizer<'a, A: Align, B: Repr, const S: usize> {
+    start: *mut MaybeUninit<B>,
+    _vector: PhantomData<Vector<A, B, S>>,
+    _slice: PhantomData<&'a mut [MaybeUninit<B>]>, // To hold the lifetime
+}
+
+// Note: The impls here assume V, B, P are Sync and Send, which they are. Nobody is able to create
+// this directly and we do have the limits on Vector, the allowed implementations, etc.
+unsafe impl<A: Align, B: Repr, const S: usize> Send for UninitVectorizer<'_, A, B, S> {}
+unsafe impl<A: Align, B: Repr, const S: usize> Sync for UninitVectorizer<'_, A, B, S> {}
+
+impl<'a, A: Align, B: Repr, const S: usize> Vectorizer<UninitProxy<'a, B, Vector<A, B, S>>>
+    for UninitVectorizer<'a, A, B, S>
+{
+    #[inline(always)]
+    unsafe fn get(&mut self, idx: usize) -> UninitProxy<'a, B, Vector<A, B, S>> {
+        // Same lifetime extension as with the WriteVectorizer, each chunk is given out only once.
+        UninitProxy {
+            target: slice::from_raw_parts_mut(self.start.add(S * idx), S),
+            _vector: PhantomData,
+        }
+    }
+}
+
+impl<'a, A: Align, B: Repr, const S: usize> Vectorizable<UninitProxy<'a, B, Vector<A, B, S>>>
+    for &'a mut [MaybeUninit<B>]
+{
+    type Vectorizer = UninitVectorizer<'a, A, B, S>;
+    // Nothing is read from the slice, so there's nothing to pad.
+    type Padding = ();
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    fn create(
+        self,
+        pad: Option<()>,
+    ) -> (
+        Self::Vectorizer,
+        usize,
+        Option<UninitProxy<'a, B, Vector<A, B, S>>>,
+    ) {
+        let len = self.len();
+        assert!(
+            mem::size_of_val(self) <= isize::MAX as usize,
+            "Slice too huge"
+        );
+        let rest = len % S;
+        let main = len - rest;
+        let start = self.as_mut_ptr();
+        let partial = match (rest, pad) {
+            (0, _) => None,
+            (_, Some(())) => Some(UninitProxy {
+                target: &mut self[main..],
+                _vector: PhantomData,
+            }),
+            _ => panic!(
+                "Data to vectorize not divisible by lanes ({} vs {})",
+                S, len,
+            ),
+        };
+        let me = UninitVectorizer {
+            start,
+            _vector: PhantomData,
+            _slice: PhantomData,
+        };
+        (me, main / S, partial)
+    }
+}
+
 macro_rules! vectorizable_tuple {
     ($(($X: ident, $XR: ident, $X0: tt)),*) => {
         impl<$($X, $XR),*> Vectorizer<($($XR),*)> for ($($X),*)
@@ -533,22 +778,41 @@ macro_rules! vectorizable_tuple {
                     Some(pad) => ($(Some(pad.$X0)),*),
                     None => Default::default(), // Bunch of Nones in a tuple.. (None, None, None)...
                 };
-                let created = ($(self.$X0.create(pad.$X0)),*);
+                let mut created = ($(self.$X0.create(pad.$X0)),*);
                 $(
                     // TODO: We may want to support this in the padded mode eventually by
                     // creating more paddings
                     assert_eq!(
-                        (created.0).1,
-                        created.$X0.1,
+                        (created.0).1 + (created.0).2.is_some() as usize,
+                        created.$X0.1 + created.$X0.2.is_some() as usize,
                         "Vectorizing data of different lengths"
                     );
+                )*
+                let padded = false $(|| created.$X0.2.is_some())*;
+                $(
+                    // The members with nothing to pad have a zero-sized padding type (vectors
+                    // are never zero-sized).
                     // TODO: We could also handle this in the padded mode by doing empty pads
-                    assert_eq!(
-                        (created.0).2.is_some(),
-                        created.$X0.2.is_some(),
+                    assert!(
+                        mem::size_of::<$X::Padding>() == 0 || created.$X0.2.is_some() == padded,
                         "Paddings are not the same for all vectorized data",
                     );
                 )*
+                if padded {
+                    $(
+                        if created.$X0.2.is_none() {
+                            // This one has nothing to pad (eg. it is already vectorized).
+                            // Its last full vector goes in tandem with the paddings of the
+                            // others.
+                            //
+                            // Safety: The index is in range (the total lengths are the same
+                            // and the others have a padding, so this one has at least one full
+                            // vector) and it's removed from the range of the iteration.
+                            created.$X0.1 -= 1;
+                            created.$X0.2 = Some(unsafe { created.$X0.0.get(created.$X0.1) });
+                        }
+                    )*
+                }
                 let vectorizer = ($(created.$X0.0),*);
                 let pad = if (created.0).2.is_some() {
                     Some(($(created.$X0.2.unwrap()),*))
@@ -592,6 +856,29 @@ vectorizable_tuple!(
     (G, GR, 6),
     (H, HR, 7)
 );
+vectorizable_tuple!(
+    (A, AR, 0),
+    (B, BR, 1),
+    (C, CR, 2),
+    (D, DR, 3),
+    (E, ER, 4),
+    (F, FR, 5),
+    (G, GR, 6),
+    (H, HR, 7),
+    (I, IR, 8)
+);
+vectorizable_tuple!(
+    (A, AR, 0),
+    (B, BR, 1),
+    (C, CR, 2),
+    (D, DR, 3),
+    (E, ER, 4),
+    (F, FR, 5),
+    (G, GR, 6),
+    (H, HR, 7),
+    (I, IR, 8),
+    (J, JR, 9)
+);
 
 impl<T, TR, const S: usize> Vectorizer<[TR; S]> for [T; S]
 where
@@ -652,7 +939,7 @@ where
     }
 }
 
-impl<'a, T> Vectorizer<T> for &'a [T]
+impl<T> Vectorizer<T> for &[T]
 where
     T: Copy,
 {
@@ -694,8 +981,87 @@ impl<'a, A: Align, B: Repr, const S: usize> Vectorizable<&'a mut Vector<A, B, S>
     }
 }
 
+/// Vectorizes an arbitrary iterator of base values.
+///
+/// This is the iterator returned by [`vectorize_iter`][crate::vectorize_iter] and
+/// [`vectorize_iter_pad`][crate::vectorize_iter_pad]. Unlike the [`Vectorizable`] slices, the
+/// source doesn't have to be contiguous in memory (or to exist as a whole at any time) ‒ the
+/// elements are pulled from the inner iterator and buffered until there's enough of them for a
+/// whole vector. This makes it usable for streaming producers, but it is also slower than
+/// vectorizing a slice.
+///
+/// The uneven end is handled the same way as with [`Vectorizable`]: in the padded variant the
+/// missing lanes of the last vector are taken from the padding, otherwise a leftover panics.
+#[derive(Clone, Debug)]
+pub struct IterVectorized<I, V> {
+    iter: iter::Fuse<I>,
+    pad: Option<V>,
+}
+
+impl<I, V> IterVectorized<I, V>
+where
+    I: Iterator,
+{
+    #[inline]
+    pub(crate) fn new(iter: I, pad: Option<V>) -> Self {
+        Self {
+            iter: iter.fuse(),
+            pad,
+        }
+    }
+}
+
+impl<I, A, B, const S: usize> Iterator for IterVectorized<I, Vector<A, B, S>>
+where
+    I: Iterator<Item = B>,
+    A: Align,
+    B: Repr,
+{
+    type Item = Vector<A, B, S>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.iter.next()?;
+        let mut result = self.pad.unwrap_or_else(|| Vector::splat(first));
+        result[0] = first;
+        for i in 1..S {
+            match self.iter.next() {
+                Some(lane) => result[i] = lane,
+                None => {
+                    assert!(
+                        self.pad.is_some(),
+                        "Iterator length isn't divisible by the vector size"
+                    );
+                    break;
+                }
+            }
+        }
+        Some(result)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        if self.pad.is_some() {
+            (lower.div_ceil(S), upper.map(|upper| upper.div_ceil(S)))
+        } else {
+            (lower / S, upper.map(|upper| upper / S))
+        }
+    }
+}
+
+impl<I, A, B, const S: usize> FusedIterator for IterVectorized<I, Vector<A, B, S>>
+where
+    I: Iterator<Item = B>,
+    A: Align,
+    B: Repr,
+{
+}
+
 #[cfg(test)]
 mod tests {
+    use core::fmt::Debug;
+
     use super::*;
     use crate::prelude::*;
 
@@ -735,4 +1101,318 @@ mod tests {
 
         assert_eq!(dst, [u16x4::new([0, 1, 2, 3]), u16x4::new([4, 5, 6, 7])]);
     }
+
+    // Members that don't need padding are combined with ones that do.
+    #[test]
+    fn iter_mixed_padding() {
+        let pre = [u32x4::splat(10), u32x4::splat(20), u32x4::splat(30)];
+        let ragged = (0..10u32).collect::<Vec<_>>();
+        let mut out = [0; 10];
+        for (p, r, mut o) in (&pre[..], &ragged[..], &mut out[..]).vectorize_pad((
+            (),
+            u32x4::splat(100),
+            u32x4::default(),
+        )) {
+            *o = p + r;
+        }
+        assert_eq!(out, [10, 11, 12, 13, 24, 25, 26, 27, 38, 39]);
+
+        let sums = (&pre[..], &ragged[..])
+            .vectorize_pad(((), u32x4::splat(100)))
+            .map(|(p, r): (u32x4, u32x4)| p + r)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sums,
+            [
+                u32x4::new([10, 11, 12, 13]),
+                u32x4::new([24, 25, 26, 27]),
+                u32x4::new([38, 39, 130, 130]),
+            ]
+        );
+
+        let mut iter = (&pre[..], &ragged[..]).vectorize_pad(((), u32x4::splat(100)));
+        let last: Option<(u32x4, u32x4)> = iter.next_back();
+        assert_eq!(last, Some((u32x4::splat(30), u32x4::new([8, 9, 100, 100]))));
+        assert_eq!(iter.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vectorizing data of different lengths")]
+    fn iter_mixed_padding_len() {
+        let pre = [u32x4::splat(10), u32x4::splat(20)];
+        let ragged = (0..10u32).collect::<Vec<_>>();
+        let _ = (&pre[..], &ragged[..]).vectorize_pad(((), u32x4::splat(0)));
+    }
+
+    // Slices producing the same number of vectors, but only one of them needs the padding.
+    #[test]
+    #[should_panic(expected = "Paddings are not the same for all vectorized data")]
+    fn iter_mixed_padding_slices() {
+        let data = (0..12u32).collect::<Vec<_>>();
+        let _ = (&data[..], &data[..9]).vectorize_pad((u32x4::splat(0), u32x4::splat(0)));
+    }
+
+    #[test]
+    fn iter_back() {
+        let data = (0..10u32).collect::<Vec<_>>();
+        let v = |a| u32x4::new(a);
+
+        let mut padded = data.vectorize_pad(u32x4::splat(99));
+        assert_eq!(padded.next_back(), Some(v([8, 9, 99, 99])));
+        assert_eq!(padded.next(), Some(v([0, 1, 2, 3])));
+        assert_eq!(padded.len(), 1);
+        assert_eq!(padded.next_back(), Some(v([4, 5, 6, 7])));
+        assert_eq!(padded.next_back(), None);
+        assert_eq!(padded.next(), None);
+
+        // The partial one is produced last from the front, even when mixing
+        let mut padded = data.vectorize_pad(u32x4::splat(99));
+        assert_eq!(padded.next(), Some(v([0, 1, 2, 3])));
+        assert_eq!(padded.next(), Some(v([4, 5, 6, 7])));
+        assert_eq!(padded.next_back(), Some(v([8, 9, 99, 99])));
+        assert_eq!(padded.next_back(), None);
+
+        let rev = data[..8].vectorize().rev().collect::<Vec<u32x4>>();
+        assert_eq!(rev, [v([4, 5, 6, 7]), v([0, 1, 2, 3])]);
+
+        let rev = data
+            .vectorize_pad(u32x4::splat(99))
+            .rev()
+            .collect::<Vec<_>>();
+        assert_eq!(rev, [v([8, 9, 99, 99]), v([4, 5, 6, 7]), v([0, 1, 2, 3])]);
+    }
+
+    // Checks nth and nth_back against skipping the items one by one, from both ends.
+    fn check_nth<I, F>(make: F)
+    where
+        F: Fn() -> I,
+        I: DoubleEndedIterator + ExactSizeIterator,
+        I::Item: Debug + PartialEq,
+    {
+        for n in 0..6 {
+            let mut iter = make();
+            let mut reference = make();
+            for _ in 0..n {
+                reference.next();
+            }
+            assert_eq!(iter.nth(n), reference.next(), "{}", n);
+            assert_eq!(iter.len(), reference.len(), "{}", n);
+            assert_eq!(iter.collect::<Vec<_>>(), reference.collect::<Vec<_>>());
+
+            let mut iter = make();
+            let mut reference = make();
+            for _ in 0..n {
+                reference.next_back();
+            }
+            assert_eq!(iter.nth_back(n), reference.next_back(), "{}", n);
+            assert_eq!(iter.len(), reference.len(), "{}", n);
+            assert_eq!(iter.collect::<Vec<_>>(), reference.collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn nth_skips() {
+        let data = (0..14u16).collect::<Vec<_>>();
+        // Lengths 3 and 4 (with partial)
+        check_nth(|| -> VectorizedIter<_, _, u16x4> { data[..12].vectorize() });
+        check_nth(|| data.vectorize_pad(u16x4::splat(99)));
+    }
+
+    #[test]
+    fn nth_back() {
+        let data = (0..14u16).collect::<Vec<_>>();
+        let mut iter = data.vectorize_pad(u16x4::default());
+        assert_eq!(iter.next(), Some(u16x4::new([0, 1, 2, 3])));
+        assert_eq!(iter.nth_back(1), Some(u16x4::new([8, 9, 10, 11])));
+        assert_eq!(iter.next_back(), Some(u16x4::new([4, 5, 6, 7])));
+        assert_eq!(iter.nth_back(0), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn nth() {
+        let data = (0..14u16).collect::<Vec<_>>();
+        let exact = || -> VectorizedIter<_, _, u16x4> { data[..12].vectorize() };
+        let padded = || data.vectorize_pad(u16x4::splat(99));
+
+        // The boundaries explicitly
+        assert_eq!(exact().nth(2), Some(u16x4::new([8, 9, 10, 11])));
+        assert_eq!(exact().nth(3), None);
+        assert_eq!(padded().nth(2), Some(u16x4::new([8, 9, 10, 11])));
+        assert_eq!(padded().nth(3), Some(u16x4::new([12, 13, 99, 99])));
+        assert_eq!(padded().nth(4), None);
+
+        let mut iter = padded();
+        assert_eq!(iter.nth(3), Some(u16x4::new([12, 13, 99, 99])));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        // The partial was already taken from the back
+        let mut iter = padded();
+        assert_eq!(iter.next_back(), Some(u16x4::new([12, 13, 99, 99])));
+        assert_eq!(iter.nth(2), Some(u16x4::new([8, 9, 10, 11])));
+        assert_eq!(iter.next(), None);
+        let mut iter = padded();
+        iter.next_back();
+        assert_eq!(iter.nth(3), None);
+        assert_eq!(iter.len(), 0);
+    }
+
+    #[test]
+    fn remainder() {
+        let data = (0..11u32).collect::<Vec<_>>();
+        let mut iter = data.vectorize_pad(u32x4::default());
+        assert_eq!(iter.remainder(), [8, 9, 10]);
+        let full = iter.by_ref().take(data.len() / 4).collect::<Vec<_>>();
+        assert_eq!(full, [u32x4::new([0, 1, 2, 3]), u32x4::new([4, 5, 6, 7])]);
+        let tail = iter.remainder().iter().sum::<u32>();
+        assert_eq!(tail, 27);
+
+        let mut even = data[..8].vectorize();
+        let _: Option<u32x4> = even.next();
+        assert!(even.remainder().is_empty());
+    }
+
+    #[test]
+    fn iter_source() {
+        let padded = crate::vectorize_iter_pad(0..10, u32x4::splat(99));
+        assert_eq!(padded.size_hint(), (3, Some(3)));
+        let padded = padded.collect::<Vec<_>>();
+        assert_eq!(
+            padded,
+            [
+                u32x4::new([0, 1, 2, 3]),
+                u32x4::new([4, 5, 6, 7]),
+                u32x4::new([8, 9, 99, 99]),
+            ]
+        );
+
+        let exact = crate::vectorize_iter::<u32x4, _>(0..8).collect::<Vec<_>>();
+        assert_eq!(exact, [u32x4::new([0, 1, 2, 3]), u32x4::new([4, 5, 6, 7])]);
+
+        // Not an exact-size source
+        let filtered = crate::vectorize_iter_pad((0..10).filter(|i| i % 3 == 0), u32x2::default());
+        assert_eq!(
+            filtered.collect::<Vec<_>>(),
+            [u32x2::new([0, 3]), u32x2::new([6, 9])]
+        );
+
+        assert_eq!(crate::vectorize_iter::<u32x4, _>(0..0).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Iterator length isn't divisible")]
+    fn iter_source_uneven() {
+        let _ = crate::vectorize_iter::<u32x4, _>(0..10).count();
+    }
+
+    #[test]
+    fn uninit() {
+        let mut buffer = [MaybeUninit::<u16>::uninit(); 8];
+        for (i, o) in (0..2).zip((&mut buffer[..]).vectorize()) {
+            let _: &UninitProxy<_, u16x4> = &o;
+            o.write(u16x4::splat(i + 1));
+        }
+        let data = buffer
+            .iter()
+            .map(|b| unsafe { b.assume_init() })
+            .collect::<Vec<_>>();
+        assert_eq!(data, [1, 1, 1, 1, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn uninit_vec() {
+        let input = (0..13).map(|i| i as f32).collect::<Vec<_>>();
+        let mut output = Vec::<f32>::with_capacity(input.len());
+        let uninit = &mut output.spare_capacity_mut()[..input.len()];
+        for (i, o) in (&input[..], uninit).vectorize_pad((f32x4::default(), ())) {
+            o.write(i + f32x4::splat(0.5));
+        }
+        unsafe { output.set_len(input.len()) };
+        assert_eq!(output, input.iter().map(|i| i + 0.5).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn mut_proxy_slice() {
+        let mut data = (0..6u32).collect::<Vec<_>>();
+        for mut v in (&mut data[..]).vectorize_pad(u32x4::splat(100)) {
+            let lanes = MutProxy::as_mut_slice(&mut v);
+            assert_eq!(lanes.len(), 4);
+            lanes[0] *= 10;
+            lanes[3] += 1;
+        }
+        assert_eq!(data, [0, 1, 2, 4, 40, 5]);
+    }
+
+    #[test]
+    fn mut_proxy_commit() {
+        let mut data = [0u32; 8];
+        {
+            let mut proxy: MutProxy<_, u32x4> = (&mut data[4..]).vectorize().next().unwrap();
+            *proxy = u32x4::splat(2);
+            proxy.commit();
+            // Still in the same scope, but already written.
+            assert_eq!(data, [0, 0, 0, 0, 2, 2, 2, 2]);
+        }
+    }
+
+    #[test]
+    fn iter_nine_tuple() {
+        let data = (0..9 * 4u32).collect::<Vec<_>>();
+        let c = |i: usize| &data[i * 4..(i + 1) * 4];
+        let input = (c(0), c(1), c(2), c(3), c(4), c(5), c(6), c(7), c(8));
+        let mut cnt = 0;
+        for (idx, v) in input.vectorize().enumerate() {
+            let (a, b, c, d, e, f, g, h, i): (
+                u32x2,
+                u32x2,
+                u32x2,
+                u32x2,
+                u32x2,
+                u32x2,
+                u32x2,
+                u32x2,
+                u32x2,
+            ) = v;
+            for (part, v) in [a, b, c, d, e, f, g, h, i].iter().enumerate() {
+                let start = (part * 4 + idx * 2) as u32;
+                assert_eq!(*v, u32x2::new([start, start + 1]));
+            }
+            cnt += 1;
+        }
+        assert_eq!(cnt, 2);
+    }
+
+    // The arrays are const-generic, so they are not limited to some small sizes.
+    #[test]
+    fn iter_long_array() {
+        let data = (0..20 * 8u32).collect::<Vec<_>>();
+        let mut slices = [&data[..0]; 20];
+        for (i, s) in slices.iter_mut().enumerate() {
+            *s = &data[i * 8..(i + 1) * 8];
+        }
+
+        let mut cnt = 0;
+        for (row, vs) in slices.vectorize().enumerate() {
+            let vs: [u32x4; 20] = vs;
+            for (i, v) in vs.iter().enumerate() {
+                let start = i as u32 * 8 + row as u32 * 4;
+                assert_eq!(*v, u32x4::new([start, start + 1, start + 2, start + 3]));
+            }
+            cnt += 1;
+        }
+        assert_eq!(cnt, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Vectorized lengths inconsistent across the array")]
+    fn iter_long_array_inconsistent() {
+        let data = [0u32; 8];
+        let mut slices = [&data[..]; 20];
+        slices[19] = &data[..4];
+        for v in slices.vectorize() {
+            let _: [u32x4; 20] = v;
+        }
+    }
 }
@@ -101,6 +101,9 @@
 //! # dot_product(&[], &[]);
 //! ```
 //!
+//! For data stored as a struct of arrays, the `Vectorize` derive macro (available with the
+//! `derive` feature) generates the code to iterate over all the fields in tandem.
+//!
 //! # Multiversioning and dynamic instruction set selection
 //!
 //! If used as in the examples above, the compiler chooses an instruction set at compile time,
@@ -109,8 +112,10 @@
 //! not being able to run the program on the older CPUs) or compile multiple versions of the same
 //! function and choose the right one at runtime, depending on what the CPU actually supports.
 //!
-//! While this library doesn't provide any direct support for multiversioning, it has been observed
-//! to work reasonably well in combination with the [`multiversion`] crate.
+//! The library provides a simple `dispatch!` macro for the common case (available with the `std`
+//! feature). It compiles the function for few well-known instruction sets and picks the best one
+//! at runtime. See the `dispatch` module for details. For more control, it has been observed to
+//! work reasonably well in combination with the [`multiversion`] crate.
 //!
 //! Note that using a newer and richer instruction set is not always a win. In some cases it can
 //! even lead to performance degradation. In particular:
@@ -253,8 +258,21 @@
 //! [`simdeez`]: https://crates.io/crates/simdeez
 //! [`safe_simd`]: https://github.com/calebzulawski/safe_simd/
 
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+use core::mem;
+
+pub mod bytes;
+pub mod checksum;
+pub mod complex;
+pub mod compute;
+#[cfg(feature = "std")]
+pub mod dispatch;
+pub mod fixed;
 pub mod iterators;
 pub mod mask;
+pub mod stats;
 pub mod types;
 pub mod vector;
 
@@ -263,11 +281,15 @@ pub use mask::Mask;
 pub use types::*;
 pub use vector::Vector;
 
+#[cfg(feature = "derive")]
+pub use slipstream_derive::Vectorize;
+
 /// Commonly used imports
 ///
 /// This can be imported to get all the vector types and all the relevant user-facing traits of the
 /// crate.
 pub mod prelude {
+    pub use crate::compute::DotReduce as _;
     pub use crate::types::*;
     pub use crate::vector::Masked as _;
     pub use crate::Mask as _;
@@ -276,8 +298,11 @@ pub mod prelude {
 
 mod inner {
     use core::num::Wrapping;
+    use core::ops::Add;
 
     use crate::mask::{m128, m16, m32, m64, m8, msize, Mask};
+    use crate::vector::align::Align;
+    use crate::Vector;
 
     /// A trait to enable vectors to use this type as the base type.
     ///
@@ -290,117 +315,308 @@ mod inner {
     pub unsafe trait Repr: Send + Sync + Copy + 'static {
         type Mask: Mask;
         const ONE: Self;
+        const ZERO: Self;
     }
 
     unsafe impl Repr for Wrapping<u8> {
         type Mask = m8;
         const ONE: Wrapping<u8> = Wrapping(1);
+        const ZERO: Wrapping<u8> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<u16> {
         type Mask = m16;
         const ONE: Wrapping<u16> = Wrapping(1);
+        const ZERO: Wrapping<u16> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<u32> {
         type Mask = m32;
         const ONE: Wrapping<u32> = Wrapping(1);
+        const ZERO: Wrapping<u32> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<u64> {
         type Mask = m64;
         const ONE: Wrapping<u64> = Wrapping(1);
+        const ZERO: Wrapping<u64> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<u128> {
         type Mask = m128;
         const ONE: Wrapping<u128> = Wrapping(1);
+        const ZERO: Wrapping<u128> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<usize> {
         type Mask = msize;
         const ONE: Wrapping<usize> = Wrapping(1);
+        const ZERO: Wrapping<usize> = Wrapping(0);
     }
     unsafe impl Repr for u8 {
         type Mask = m8;
         const ONE: u8 = 1;
+        const ZERO: u8 = 0;
     }
     unsafe impl Repr for u16 {
         type Mask = m16;
         const ONE: u16 = 1;
+        const ZERO: u16 = 0;
     }
     unsafe impl Repr for u32 {
         type Mask = m32;
         const ONE: u32 = 1;
+        const ZERO: u32 = 0;
     }
     unsafe impl Repr for u64 {
         type Mask = m64;
         const ONE: u64 = 1;
+        const ZERO: u64 = 0;
     }
     unsafe impl Repr for u128 {
         type Mask = m128;
         const ONE: u128 = 1;
+        const ZERO: u128 = 0;
     }
     unsafe impl Repr for usize {
         type Mask = msize;
         const ONE: usize = 1;
+        const ZERO: usize = 0;
     }
 
     unsafe impl Repr for Wrapping<i8> {
         type Mask = m8;
         const ONE: Wrapping<i8> = Wrapping(1);
+        const ZERO: Wrapping<i8> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<i16> {
         type Mask = m16;
         const ONE: Wrapping<i16> = Wrapping(1);
+        const ZERO: Wrapping<i16> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<i32> {
         type Mask = m32;
         const ONE: Wrapping<i32> = Wrapping(1);
+        const ZERO: Wrapping<i32> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<i64> {
         type Mask = m64;
         const ONE: Wrapping<i64> = Wrapping(1);
+        const ZERO: Wrapping<i64> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<i128> {
         type Mask = m128;
         const ONE: Wrapping<i128> = Wrapping(1);
+        const ZERO: Wrapping<i128> = Wrapping(0);
     }
     unsafe impl Repr for Wrapping<isize> {
         type Mask = msize;
         const ONE: Wrapping<isize> = Wrapping(1);
+        const ZERO: Wrapping<isize> = Wrapping(0);
     }
     unsafe impl Repr for i8 {
         type Mask = m8;
         const ONE: i8 = 1;
+        const ZERO: i8 = 0;
     }
     unsafe impl Repr for i16 {
         type Mask = m16;
         const ONE: i16 = 1;
+        const ZERO: i16 = 0;
     }
     unsafe impl Repr for i32 {
         type Mask = m32;
         const ONE: i32 = 1;
+        const ZERO: i32 = 0;
     }
     unsafe impl Repr for i64 {
         type Mask = m64;
         const ONE: i64 = 1;
+        const ZERO: i64 = 0;
     }
     unsafe impl Repr for i128 {
         type Mask = m128;
         const ONE: i128 = 1;
+        const ZERO: i128 = 0;
     }
     unsafe impl Repr for isize {
         type Mask = msize;
         const ONE: isize = 1;
+        const ZERO: isize = 0;
     }
 
     unsafe impl Repr for f32 {
         type Mask = m32;
         const ONE: f32 = 1.0;
+        const ZERO: f32 = 0.0;
     }
     unsafe impl Repr for f64 {
         type Mask = m64;
         const ONE: f64 = 1.0;
+        const ZERO: f64 = 0.0;
     }
     unsafe impl<M: Mask> Repr for M {
         type Mask = Self;
         const ONE: M = M::TRUE;
+        const ZERO: M = M::FALSE;
+    }
+
+    /// Base types for which any bit pattern is a valid value.
+    ///
+    /// # Safety
+    ///
+    /// Implementing it for a type which doesn't allow arbitrary bit patterns (like the masks or
+    /// `bool`) would allow creating invalid values from bytes.
+    pub unsafe trait Pod: Repr {}
+
+    macro_rules! pod {
+        ($($t: ty),*) => {
+            $(
+                unsafe impl Pod for $t {}
+                unsafe impl Pod for Wrapping<$t> {}
+            )*
+        };
+    }
+
+    pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+    unsafe impl Pod for f32 {}
+    unsafe impl Pod for f64 {}
+
+    /// Numeric base types with a notion of a median of even number of values.
+    pub trait Median: Repr + PartialOrd {
+        /// Picks the median from the two middle values (`lo <= hi`).
+        fn middle(lo: Self, hi: Self) -> Self;
+    }
+
+    macro_rules! median_lower {
+        ($($t: ty),*) => {
+            $(
+                impl Median for $t {
+                    #[inline]
+                    fn middle(lo: Self, _hi: Self) -> Self {
+                        lo
+                    }
+                }
+                impl Median for Wrapping<$t> {
+                    #[inline]
+                    fn middle(lo: Self, _hi: Self) -> Self {
+                        lo
+                    }
+                }
+            )*
+        };
+    }
+
+    median_lower!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+    impl Median for f32 {
+        #[inline]
+        fn middle(lo: Self, hi: Self) -> Self {
+            lo / 2.0 + hi / 2.0
+        }
+    }
+
+    impl Median for f64 {
+        #[inline]
+        fn middle(lo: Self, hi: Self) -> Self {
+            lo / 2.0 + hi / 2.0
+        }
+    }
+
+    /// Signed primitive integers.
+    ///
+    /// Exposes the inherent methods that have no counterpart in `num_traits`.
+    pub trait SignedInt: Repr {
+        fn wrapping_neg(self) -> Self;
+        fn wrapping_abs(self) -> Self;
+    }
+
+    macro_rules! signed_int {
+        ($($t: ty),*) => {
+            $(
+                impl SignedInt for $t {
+                    #[inline]
+                    fn wrapping_neg(self) -> Self {
+                        <$t>::wrapping_neg(self)
+                    }
+                    #[inline]
+                    fn wrapping_abs(self) -> Self {
+                        <$t>::wrapping_abs(self)
+                    }
+                }
+            )*
+        };
+    }
+
+    signed_int!(i8, i16, i32, i64, i128, isize);
+
+    /// Integers with a twice as wide counterpart.
+    pub trait WideMul: Repr {
+        type Wide: Repr;
+        /// The full product.
+        fn mul_wide(self, other: Self) -> Self::Wide;
+        /// The high half of the full product.
+        fn mul_hi(self, other: Self) -> Self;
+        /// The full product, shifted right by `shift` bits (rounding to nearest) and truncated.
+        fn mul_shr(self, other: Self, shift: u32) -> Self;
+    }
+
+    macro_rules! wide_mul {
+        ($(($t: ty, $wide: ty)),*) => {
+            $(
+                impl WideMul for $t {
+                    type Wide = $wide;
+                    #[inline]
+                    fn mul_wide(self, other: Self) -> $wide {
+                        self as $wide * other as $wide
+                    }
+                    #[inline]
+                    fn mul_hi(self, other: Self) -> Self {
+                        let product = self as $wide * other as $wide;
+                        (product >> <$t>::BITS) as $t
+                    }
+                    #[inline]
+                    fn mul_shr(self, other: Self, shift: u32) -> Self {
+                        let product = self as $wide * other as $wide;
+                        let half = if shift == 0 { 0 } else { 1 << (shift - 1) };
+                        ((product + half) >> shift) as $t
+                    }
+                }
+            )*
+        };
+    }
+
+    wide_mul!(
+        (u8, u16),
+        (u16, u32),
+        (u32, u64),
+        (u64, u128),
+        (i8, i16),
+        (i16, i32),
+        (i32, i64),
+        (i64, i128)
+    );
+
+    /// Vector types, tied to the base type of their lanes.
+    ///
+    /// # Safety
+    ///
+    /// The type must consist of `LANES` instances of `B` and nothing else (if its size is
+    /// `LANES * size_of::<B>()`, there's no padding).
+    pub unsafe trait VectorOf<B>: Copy {
+        const LANES: usize;
+
+        /// The [`Vector::horizontal_sum`], usable in code generic over the vector type.
+        fn horizontal_sum(self) -> B
+        where
+            B: Add<Output = B>;
+    }
+
+    unsafe impl<A: Align, B: Repr, const S: usize> VectorOf<B> for Vector<A, B, S> {
+        const LANES: usize = S;
+
+        #[inline]
+        fn horizontal_sum(self) -> B
+        where
+            B: Add<Output = B>,
+        {
+            Vector::horizontal_sum(self)
+        }
     }
 }
 
@@ -442,10 +658,277 @@ where
     a.vectorize_pad(pad)
 }
 
+/// Vectorizes an arbitrary iterator.
+///
+/// The elements are buffered into vectors of type `V` and yielded one whole vector at a time.
+/// This allows using vectors with sources that are not slices ‒ for example data read
+/// incrementally from a file or received through a channel. Prefer the [`Vectorizable`] on
+/// slices when possible, as it is faster.
+///
+/// Like [`vectorize`], this assumes the number of elements is divisible by the vector size.
+///
+/// # Panics
+///
+/// During the iteration, if the source ends in the middle of a vector.
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let total = slipstream::vectorize_iter::<u32x4, _>((0..8).map(|i| i * 2))
+///     .sum::<u32x4>();
+/// assert_eq!(total, u32x4::new([8, 12, 16, 20]));
+/// ```
+#[inline]
+pub fn vectorize_iter<V, I>(iter: I) -> iterators::IterVectorized<I, V>
+where
+    I: Iterator,
+    iterators::IterVectorized<I, V>: Iterator<Item = V>,
+{
+    iterators::IterVectorized::new(iter, None)
+}
+
+/// Vectorizes an arbitrary iterator, padding the odd end if needed.
+///
+/// This is like [`vectorize_iter`], but if the source ends in the middle of a vector, the missing
+/// lanes are taken from the `pad` (like with [`vectorize_pad`]).
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let v = slipstream::vectorize_iter_pad(1..=6, i32x4::splat(-1)).collect::<Vec<_>>();
+/// assert_eq!(v, vec![i32x4::new([1, 2, 3, 4]), i32x4::new([5, 6, -1, -1])]);
+/// ```
+#[inline]
+pub fn vectorize_iter_pad<V, I>(iter: I, pad: V) -> iterators::IterVectorized<I, V>
+where
+    I: Iterator,
+    iterators::IterVectorized<I, V>: Iterator<Item = V>,
+{
+    iterators::IterVectorized::new(iter, Some(pad))
+}
+
+/// Vectorizes a 2D buffer, row by row.
+///
+/// The `data` is a row-major image (or other 2D grid) of `height` rows, each `width` elements
+/// long. This produces an iterator of the rows, each of them being an iterator of vectors. The
+/// rows are vectorized separately, so a vector never spills from the end of one row into the
+/// beginning of the next one. Instead, the last vector of each row is padded by the `pad` (if the
+/// width is not divisible by the vector size).
+///
+/// # Examples
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let image = [
+///     1, 2, 3,
+///     4, 5, 6,
+/// ];
+/// let rows = slipstream::vectorize_2d(&image[..], 3, 2, u32x2::default())
+///     .map(|row| row.collect::<Vec<_>>())
+///     .collect::<Vec<_>>();
+/// assert_eq!(rows, vec![
+///     vec![u32x2::new([1, 2]), u32x2::new([3, 0])],
+///     vec![u32x2::new([4, 5]), u32x2::new([6, 0])],
+/// ]);
+/// ```
+///
+/// # Panics
+///
+/// If the length of the data doesn't match the dimensions.
+#[inline]
+pub fn vectorize_2d<'a, V, B>(
+    data: &'a [B],
+    width: usize,
+    height: usize,
+    pad: V,
+) -> impl Iterator<Item = impl Iterator<Item = V> + 'a> + 'a
+where
+    V: Copy + 'a,
+    &'a [B]: Vectorizable<V, Padding = V>,
+{
+    assert_eq!(
+        data.len(),
+        width * height,
+        "Data length doesn't match the dimensions"
+    );
+    (0..height).map(move |y| data[y * width..(y + 1) * width].vectorize_pad(pad))
+}
+
+/// Splits a slice into an unaligned prefix, a slice of vectors and a suffix.
+///
+/// This is similar to [`slice::align_to`], but safe. The middle part is the biggest part of the
+/// slice that is aligned for the vector `V` and can be reinterpreted as vectors without any
+/// copying. Unlike with [`vectorize`][Vectorizable::vectorize], the middle is a real slice of
+/// vectors, which is friendlier to the optimizer. The prefix and suffix are shorter than one
+/// vector and need to be handled separately.
+///
+/// # Examples
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let data = (0..100).collect::<Vec<u32>>();
+/// let (prefix, middle, suffix) = slipstream::as_vectors::<u32x4, _>(&data);
+/// assert!(prefix.len() < 4);
+/// assert!(suffix.len() < 4);
+/// assert_eq!(prefix.len() + middle.len() * 4 + suffix.len(), 100);
+/// let sum = prefix.iter().sum::<u32>()
+///     + middle.iter().copied().sum::<u32x4>().horizontal_sum()
+///     + suffix.iter().sum::<u32>();
+/// assert_eq!(sum, 4950);
+/// ```
+///
+/// # Panics
+///
+/// If the vector type contains padding (the alignment is bigger than the size of the vector).
+#[inline]
+pub fn as_vectors<V, B>(slice: &[B]) -> (&[B], &[V], &[B])
+where
+    V: inner::VectorOf<B>,
+{
+    assert_eq!(
+        mem::size_of::<V>(),
+        V::LANES * mem::size_of::<B>(),
+        "Must not contain paddings/invalid Align parameter",
+    );
+    // Safety: V is an array of B (checked above) and any bit pattern of B is valid in V.
+    unsafe { slice.align_to() }
+}
+
+/// Splits a mutable slice into an unaligned prefix, a slice of vectors and a suffix.
+///
+/// This is the mutable counterpart of [`as_vectors`]. As the middle is a real slice of vectors,
+/// the modifications go directly to the original slice, without the copying done by
+/// [`MutProxy`][iterators::MutProxy].
+///
+/// # Examples
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let mut data = (0..100).collect::<Vec<u32>>();
+/// let (prefix, middle, suffix) = slipstream::as_vectors_mut::<u32x4, _>(&mut data);
+/// for p in prefix.iter_mut().chain(suffix) {
+///     *p *= 2;
+/// }
+/// for m in middle {
+///     *m *= u32x4::splat(2);
+/// }
+/// assert_eq!(data, (0..100).map(|i| i * 2).collect::<Vec<_>>());
+/// ```
+///
+/// # Panics
+///
+/// If the vector type contains padding (the alignment is bigger than the size of the vector).
+#[inline]
+pub fn as_vectors_mut<V, B>(slice: &mut [B]) -> (&mut [B], &mut [V], &mut [B])
+where
+    V: inner::VectorOf<B>,
+{
+    assert_eq!(
+        mem::size_of::<V>(),
+        V::LANES * mem::size_of::<B>(),
+        "Must not contain paddings/invalid Align parameter",
+    );
+    // Safety: V is an array of B (checked above) and any bit pattern of B is valid in V. Also,
+    // any bit pattern of V is a valid array of B.
+    unsafe { slice.align_to_mut() }
+}
+
 #[cfg(test)]
 mod tests {
+    use core::slice;
+
     use crate::prelude::*;
 
+    fn aligned_data() -> [u32x4; 4] {
+        let mut data = [u32x4::default(); 4];
+        for (i, v) in data.iter_mut().enumerate() {
+            for (j, l) in v.iter_mut().enumerate() {
+                *l = (i * 4 + j) as u32;
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn vectorize_2d() {
+        let (width, height) = (10, 7);
+        let data = (0..width * height)
+            .map(|i| (i * 13 % 29) as u32)
+            .collect::<Vec<_>>();
+        let mut total = 0;
+        let mut rows = 0;
+        for (row, vectors) in
+            data.chunks(width)
+                .zip(crate::vectorize_2d(&data, width, height, u32x4::default()))
+        {
+            let vectors = vectors.collect::<Vec<_>>();
+            assert_eq!(vectors.len(), 3);
+            // Nothing from the next row leaked into the padding
+            assert_eq!(&vectors[2][2..], &[0, 0]);
+            let sum = vectors.into_iter().sum::<u32x4>().horizontal_sum();
+            assert_eq!(sum, row.iter().sum::<u32>());
+            total += sum;
+            rows += 1;
+        }
+        assert_eq!(rows, height);
+        assert_eq!(total, data.iter().sum::<u32>());
+
+        assert_eq!(
+            crate::vectorize_2d(&[0u32; 0], 0, 3, u32x4::default()).count(),
+            3
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Data length doesn't match the dimensions")]
+    fn vectorize_2d_size() {
+        let _ = crate::vectorize_2d(&[0u32; 5], 2, 2, u32x4::default());
+    }
+
+    #[test]
+    fn as_vectors_aligned() {
+        let data = aligned_data();
+        let flat: &[u32] = unsafe { slice::from_raw_parts(data.as_ptr().cast(), 16) };
+        let (prefix, middle, suffix) = crate::as_vectors::<u32x4, _>(flat);
+        assert!(prefix.is_empty());
+        assert_eq!(middle, data);
+        assert!(suffix.is_empty());
+    }
+
+    #[test]
+    fn as_vectors_misaligned() {
+        let data = aligned_data();
+        let flat: &[u32] = unsafe { slice::from_raw_parts(data.as_ptr().cast(), 16) };
+        let part = &flat[1..14];
+        let (prefix, middle, suffix) = crate::as_vectors::<u32x4, _>(part);
+        assert_eq!(prefix, [1, 2, 3]);
+        assert_eq!(middle, &data[1..3]);
+        assert_eq!(suffix, [12, 13]);
+
+        let rebuilt = prefix
+            .iter()
+            .chain(middle.iter().flat_map(|v| v.iter()))
+            .chain(suffix)
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(rebuilt, part);
+    }
+
+    #[test]
+    fn as_vectors_mut() {
+        let mut data = aligned_data();
+        let flat: &mut [u32] = unsafe { slice::from_raw_parts_mut(data.as_mut_ptr().cast(), 16) };
+        let (prefix, middle, suffix) = crate::as_vectors_mut::<u32x4, _>(&mut flat[2..]);
+        assert_eq!(prefix.len(), 2);
+        assert_eq!(middle.len(), 3);
+        assert!(suffix.is_empty());
+        prefix[0] = 100;
+        middle[0] = u32x4::splat(42);
+        middle[2] += u32x4::splat(1);
+        assert_eq!(data[0], u32x4::new([0, 1, 100, 3]));
+        assert_eq!(data[1], u32x4::splat(42));
+        assert_eq!(data[2], u32x4::new([8, 9, 10, 11]));
+        assert_eq!(data[3], u32x4::new([13, 14, 15, 16]));
+    }
+
     #[test]
     fn minmax() {
         let a = u32x4::new([1, 4, 8, 9]);
@@ -0,0 +1,113 @@
+//! Basic statistics over slices.
+//!
+//! Similar to the [`compute`][crate::compute] module, the functions need to be told what vector
+//! type to use, usually with a turbofish.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use slipstream::prelude::*;
+//! use slipstream::stats;
+//!
+//! let (mean, var) = stats::mean_var::<f64x4, _>(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+//! assert_eq!(mean, 3.0);
+//! assert_eq!(var, 2.0);
+//! ```
+
+use num_traits::Float;
+
+use crate::inner::{Repr, VectorOf};
+use crate::Vectorizable;
+
+/// Computes the mean and the (population) variance of the data in a single pass.
+///
+/// The data are split into vectors of type `V`. The sums of the values and of their squares are
+/// accumulated lane-wise and reduced only once at the end. To avoid the catastrophic cancellation
+/// the naive formula suffers from when the mean is far from zero, all the values are first shifted
+/// by the first element of the data (the sums are of the differences from it), which is corrected
+/// for at the end.
+///
+/// The variance is the population one (divided by the number of elements, not by one less). If
+/// the data are empty, both results are `NaN`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let data = (0..1000).map(|i| 1e6 + (i % 3) as f32).collect::<Vec<_>>();
+/// let (mean, var) = slipstream::stats::mean_var::<f32x8, _>(&data);
+/// assert!((mean - 1_000_000.999).abs() < 0.1);
+/// assert!((var - 0.667).abs() < 0.01);
+/// ```
+#[inline]
+pub fn mean_var<V, B>(data: &[B]) -> (B, B)
+where
+    B: Repr + Float,
+    V: VectorOf<B> + Default + AsRef<[B]> + AsMut<[B]>,
+    for<'a> &'a [B]: Vectorizable<V, Padding = V>,
+{
+    let shift = data.first().copied().unwrap_or_else(B::zero);
+    // The padding is the shift, so it contributes zeroes to both sums.
+    let mut pad = V::default();
+    for p in pad.as_mut() {
+        *p = shift;
+    }
+    let mut sum = V::default();
+    let mut sum_sq = V::default();
+    for v in data.vectorize_pad(pad) {
+        for ((s, sq), &x) in sum.as_mut().iter_mut().zip(sum_sq.as_mut()).zip(v.as_ref()) {
+            let d = x - shift;
+            *s = *s + d;
+            *sq = d.mul_add(d, *sq);
+        }
+    }
+    let n = B::from(data.len()).unwrap();
+    let sum = sum.horizontal_sum();
+    let sum_sq = sum_sq.horizontal_sum();
+    let mean = shift + sum / n;
+    let var = (sum_sq - sum * sum / n) / n;
+    // Rounding errors could make it slightly negative
+    let var = if var < B::zero() { B::zero() } else { var };
+    (mean, var)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn two_pass(data: &[f64]) -> (f64, f64) {
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let var = data.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+        (mean, var)
+    }
+
+    #[test]
+    fn mean_var_scalar() {
+        for &offset in &[0.0, -50.0, 1e8] {
+            for &len in &[1, 3, 8, 100, 1001] {
+                let data = (0..len)
+                    .map(|i| offset + ((i * 7 % 13) as f64) / 4.0)
+                    .collect::<Vec<_>>();
+                let (mean, var) = two_pass(&data);
+                for &(m, v) in &[mean_var::<f64x4, _>(&data), mean_var::<f64x8, _>(&data)] {
+                    assert!(
+                        (m - mean).abs() <= 1e-9 * mean.abs().max(1.0),
+                        "{} {}",
+                        m,
+                        mean
+                    );
+                    assert!((v - var).abs() <= 1e-9 * var.max(1.0), "{} {}", v, var);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mean_var_empty() {
+        let (mean, var) = mean_var::<f32x4, _>(&[]);
+        assert!(mean.is_nan());
+        assert!(var.is_nan());
+    }
+}
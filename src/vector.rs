@@ -17,15 +17,19 @@
 //! These types aliases are not thoroughly documented on themselves. The documentation is on the
 //! [`Vector`]. A lot of its functionality is in traits it implements.
 
-use core::fmt::{Debug, Formatter, Result as FmtResult};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
 use core::iter::{Product, Sum};
 use core::mem::{self, MaybeUninit};
 use core::ops::*;
 use core::ptr;
-use num_traits::Float;
+use core::slice;
+use num_traits::float::TotalOrder;
+use num_traits::{Float, PrimInt, Unsigned};
 
 use self::align::Align;
-use crate::inner::Repr;
+use crate::inner::{Median, Pod, Repr, SignedInt, WideMul};
 use crate::Mask;
 
 /// Enforcement of alignment.
@@ -81,6 +85,15 @@ pub trait Masked {
     type Mask;
 }
 
+/// Indices known at compile time.
+///
+/// Used by [`gather_const`][Vector::gather_const] to shuffle lanes without any runtime indices.
+/// Implement it on a (usually empty) marker type, see the example there.
+pub trait ConstIndices<const S: usize> {
+    /// The indices to load the lanes from.
+    const IDX: [usize; S];
+}
+
 macro_rules! bin_op_impl {
     ($tr: ident, $meth: ident, $tr_assign: ident, $meth_assign: ident) => {
         impl<A: Align, B: $tr<Output = B> + Repr, const S: usize> $tr for Vector<A, B, S> {
@@ -182,6 +195,158 @@ macro_rules! cmp_op {
     };
 }
 
+macro_rules! cmp_mask_op {
+    ($($(#[ $meta: meta ])* $tr: ident => $op: ident, $name: ident;)*) => {
+        $(
+            $(#[ $meta ])*
+            #[inline]
+            pub fn $name(self, other: Self) -> u32
+            where
+                B: $tr,
+            {
+                #[allow(clippy::let_unit_value)]
+                let () = BitmaskCheck::<S>::FITS;
+                let mut result = 0;
+                for i in 0..S {
+                    if self.data[i].$op(&other.data[i]) {
+                        result |= 1 << i;
+                    }
+                }
+                result
+            }
+        )*
+    };
+}
+
+macro_rules! masked_op {
+    ($($(#[ $meta: meta ])* $tr: ident, $op: ident => $name: ident;)*) => {
+        $(
+            $(#[ $meta ])*
+            #[inline]
+            pub fn $name<M, MB>(self, other: Self, mask: M) -> Self
+            where
+                B: $tr<Output = B>,
+                M: AsRef<[MB]>,
+                MB: Mask,
+            {
+                self.blend($tr::$op(self, other), mask)
+            }
+        )*
+    };
+}
+
+// Sorting networks, as layers of independent comparators. Optimal ones (in both the number of
+// comparators and layers) for up to 8 lanes, Batcher's odd-even merge sort for 16.
+type Network = &'static [&'static [(usize, usize)]];
+
+const SORT_2: Network = &[&[(0, 1)]];
+const SORT_4: Network = &[&[(0, 1), (2, 3)], &[(0, 2), (1, 3)], &[(1, 2)]];
+const SORT_8: Network = &[
+    &[(0, 1), (2, 3), (4, 5), (6, 7)],
+    &[(0, 2), (1, 3), (4, 6), (5, 7)],
+    &[(1, 2), (5, 6)],
+    &[(0, 4), (1, 5), (2, 6), (3, 7)],
+    &[(2, 4), (3, 5)],
+    &[(1, 2), (3, 4), (5, 6)],
+];
+const SORT_16: Network = &[
+    &[
+        (0, 1),
+        (2, 3),
+        (4, 5),
+        (6, 7),
+        (8, 9),
+        (10, 11),
+        (12, 13),
+        (14, 15),
+    ],
+    &[
+        (0, 2),
+        (1, 3),
+        (4, 6),
+        (5, 7),
+        (8, 10),
+        (9, 11),
+        (12, 14),
+        (13, 15),
+    ],
+    &[(1, 2), (5, 6), (9, 10), (13, 14)],
+    &[
+        (0, 4),
+        (1, 5),
+        (2, 6),
+        (3, 7),
+        (8, 12),
+        (9, 13),
+        (10, 14),
+        (11, 15),
+    ],
+    &[(2, 4), (3, 5), (10, 12), (11, 13)],
+    &[(1, 2), (3, 4), (5, 6), (9, 10), (11, 12), (13, 14)],
+    &[
+        (0, 8),
+        (1, 9),
+        (2, 10),
+        (3, 11),
+        (4, 12),
+        (5, 13),
+        (6, 14),
+        (7, 15),
+    ],
+    &[(4, 8), (5, 9), (6, 10), (7, 11)],
+    &[(2, 4), (3, 5), (6, 8), (7, 9), (10, 12), (11, 13)],
+    &[(1, 2), (3, 4), (5, 6), (7, 8), (9, 10), (11, 12), (13, 14)],
+];
+
+// Compile time checks of sizes when converting between vectors of different number of lanes.
+struct SizeCheck<const S: usize, const N: usize, const C: usize>;
+
+impl<const S: usize, const N: usize, const C: usize> SizeCheck<S, N, C> {
+    const CHUNKS: () = assert!(N * C == S, "The chunks don't match the size of the vector");
+}
+
+struct BitmaskCheck<const S: usize>;
+
+impl<const S: usize> BitmaskCheck<S> {
+    const FITS: () = assert!(S <= 32, "Too many lanes to fit into a bitmask");
+}
+
+struct ExtendCheck<B, const BITS: u32>(B);
+
+impl<B, const BITS: u32> ExtendCheck<B, BITS> {
+    const FITS: () = assert!(
+        BITS > 0 && BITS as usize <= mem::size_of::<B>() * 8,
+        "The extended field doesn't fit into the lane"
+    );
+}
+
+struct IndicesCheck<I, const S: usize, const L: usize>(I);
+
+impl<I: ConstIndices<S>, const S: usize, const L: usize> IndicesCheck<I, S, L> {
+    const IN_BOUNDS: () = {
+        let mut i = 0;
+        while i < S {
+            assert!(I::IDX[i] < L, "Gather out of bounds");
+            i += 1;
+        }
+    };
+}
+
+struct LayoutCheck<A, B, const S: usize>(A, B);
+
+impl<A: Align, B: Repr, const S: usize> LayoutCheck<A, B, S> {
+    const VALID: () = assert!(
+        S > 0 && mem::size_of::<Vector<A, B, S>>() == mem::size_of::<[B; S]>(),
+        "Must not contain paddings/invalid Align parameter"
+    );
+}
+
+pub(crate) struct DivisibleCheck<const S: usize, const N: usize>;
+
+impl<const S: usize, const N: usize> DivisibleCheck<S, N> {
+    pub(crate) const DIVISIBLE: () = assert!(N > 0 && S / N * N == S, "Vector sizes not divisible");
+}
+
 /// A vector type.
 ///
 /// Vector types are mostly well aligned fixed sized arrays. Unlike the arrays, they have the usual
@@ -229,6 +394,75 @@ where
     /// Number of lanes of the vector.
     pub const LANES: usize = S;
 
+    /// A vector with all lanes set to zero.
+    ///
+    /// This is the same as the [`Default`] value of numeric vectors (for masks, the lanes are
+    /// false), but usable in constant contexts and generic code.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let sum = [1, 2, 3, 4, 5, 6, 7, 8]
+    ///     .vectorize()
+    ///     .fold(u32x4::ZERO, |acc, v| acc + v);
+    /// assert_eq!(sum, u32x4::new([6, 8, 10, 12]));
+    /// ```
+    ///
+    /// Invalid vector types (eg. ones with padding) are detected during compilation:
+    ///
+    /// ```rust,compile_fail
+    /// use slipstream::vector::align::Align16;
+    /// use slipstream::Vector;
+    ///
+    /// let v = Vector::<Align16, u8, 2>::ZERO;
+    /// ```
+    pub const ZERO: Self = {
+        #[allow(clippy::let_unit_value)]
+        let () = LayoutCheck::<A, B, S>::VALID;
+        Self {
+            _align: [],
+            data: [B::ZERO; S],
+        }
+    };
+
+    /// A vector with all lanes set to one.
+    ///
+    /// For masks, the lanes are true.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// assert_eq!(f32x2::ONE, f32x2::splat(1.0));
+    /// ```
+    pub const ONE: Self = {
+        #[allow(clippy::let_unit_value)]
+        let () = LayoutCheck::<A, B, S>::VALID;
+        Self {
+            _align: [],
+            data: [B::ONE; S],
+        }
+    };
+
+    /// The number of lanes of the vector.
+    ///
+    /// This is the same as [`LANES`][Vector::LANES], but reads better in some generic code.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// assert_eq!(u32x4::default().len(), 4);
+    /// ```
+    #[inline]
+    pub const fn len(&self) -> usize {
+        S
+    }
+
+    /// Checks if the vector has no lanes.
+    ///
+    /// This is always false, since zero-sized vectors can't be created. It exists for consistency
+    /// with [`len`][Vector::len].
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        S == 0
+    }
+
     #[inline(always)]
     fn assert_size() {
         assert!(S > 0);
@@ -292,6 +526,41 @@ where
         unsafe { Self::new_unchecked(input.as_ptr()) }
     }
 
+    /// Reinterprets a reference to an array as a reference to a vector, without copying.
+    ///
+    /// This succeeds only if the array happens to be aligned enough for the vector. Otherwise,
+    /// `None` is returned and the caller needs to fall back to copying (eg. with
+    /// [`new`][Vector::new]).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// // The inside of a vector is certainly aligned
+    /// let arr: &[u32; 4] = &v;
+    /// assert_eq!(u32x4::try_from_array_ref(arr), Some(&v));
+    ///
+    /// let arr = [1, 2, 3, 4];
+    /// let v = u32x4::try_from_array_ref(&arr)
+    ///     .copied()
+    ///     .unwrap_or_else(|| u32x4::new(arr));
+    /// assert_eq!(v, arr);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the vector type contains padding (the alignment is bigger than the size of the vector).
+    #[inline]
+    pub fn try_from_array_ref(arr: &[B; S]) -> Option<&Self> {
+        Self::assert_size();
+        if arr.as_ptr() as usize & (mem::align_of::<Self>() - 1) == 0 {
+            // Safety: The vector is just the array with bigger alignment (no padding checked
+            // above) and the alignment was just checked.
+            Some(unsafe { &*(arr as *const [B; S]).cast::<Self>() })
+        } else {
+            None
+        }
+    }
+
     // TODO: Can we turn it into const fn?
     /// Produces a vector of all lanes set to the same value.
     ///
@@ -309,6 +578,84 @@ where
         }
     }
 
+    /// Produces a vector of all lanes set to the same value.
+    ///
+    /// This is an alias of [`splat`][Vector::splat], under the name used by some other SIMD
+    /// libraries.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x4::broadcast(1.2);
+    /// assert_eq!(v, f32x4::new([1.2, 1.2, 1.2, 1.2]));
+    /// ```
+    #[inline]
+    pub fn broadcast(value: B) -> Self {
+        Self::splat(value)
+    }
+
+    /// Iterates over the lanes of the vector.
+    ///
+    /// This is the same as iterating through the dereferenced array, but doesn't rely on the
+    /// deref coercion (which doesn't kick in in some generic contexts).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// assert_eq!(v.iter().sum::<u32>(), 10);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, B> {
+        self.data.iter()
+    }
+
+    /// Iterates over mutable references to the lanes of the vector.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let mut v = u32x4::new([1, 2, 3, 4]);
+    /// for (i, lane) in v.iter_mut().enumerate() {
+    ///     *lane *= i as u32;
+    /// }
+    /// assert_eq!(v, u32x4::new([0, 2, 6, 12]));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, B> {
+        self.data.iter_mut()
+    }
+
+    /// Returns a raw pointer to the first lane.
+    ///
+    /// The lanes are stored consecutively, as an array. The pointer is aligned to the alignment of
+    /// the vector (given by the alignment marker), which may be larger than the alignment of the
+    /// base type. This is suitable for passing the vector to FFI functions expecting a pointer to
+    /// an array or to the explicit intrinsics in [`core::arch`].
+    #[inline]
+    pub fn as_ptr(&self) -> *const B {
+        self.data.as_ptr()
+    }
+
+    /// Returns a raw mutable pointer to the first lane.
+    ///
+    /// See [`as_ptr`][Vector::as_ptr] for details.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut B {
+        self.data.as_mut_ptr()
+    }
+
+    /// Views the vector as raw bytes.
+    ///
+    /// The length of the result is `S * size_of::<B>()`. The lanes are in the native endianness.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u16x2::new([1, 2]);
+    /// assert_eq!(v.as_bytes(), &[1u16.to_ne_bytes(), 2u16.to_ne_bytes()].concat()[..]);
+    /// ```
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.as_ptr().cast(), mem::size_of_val(&self.data)) }
+    }
+
     /// Loads the vector from a slice by indexing it.
     ///
     /// Unlike [`new`], this can load the vector from discontinuous parts of the slice, out of
@@ -354,6 +701,34 @@ where
     /// [`new`]: Vector::new
     #[inline]
     pub fn gather_load<I, Idx>(input: I, idx: Idx) -> Self
+    where
+        I: AsRef<[B]>,
+        Idx: AsRef<[usize]>,
+    {
+        match Self::try_gather_load(input, idx) {
+            Ok(result) => result,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// A fallible version of [`gather_load`][Vector::gather_load].
+    ///
+    /// Instead of panicking on an index out of bounds of the `input`, this returns an error
+    /// describing the first such index. This is useful when the indices come from untrusted data.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let input = [10, 20, 30];
+    /// assert_eq!(u32x2::try_gather_load(input, [2, 0]), Ok(u32x2::new([30, 10])));
+    /// let err = u32x2::try_gather_load(input, [1, 5]).unwrap_err();
+    /// assert_eq!((err.lane, err.index), (1, 5));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the `idx` slice doesn't have the same length as the vector.
+    #[inline]
+    pub fn try_gather_load<I, Idx>(input: I, idx: Idx) -> Result<Self, GatherError>
     where
         I: AsRef<[B]>,
         Idx: AsRef<[usize]>,
@@ -366,7 +741,13 @@ where
             idx.len(),
             "Gathering vector from wrong number of indexes"
         );
-        assert!(idx.iter().all(|&l| l < input.len()), "Gather out of bounds");
+        if let Some(lane) = idx.iter().position(|&l| l >= input.len()) {
+            return Err(GatherError {
+                lane,
+                index: idx[lane],
+                len: input.len(),
+            });
+        }
         let mut data = MaybeUninit::<Self>::uninit();
         unsafe {
             for i in 0..S {
@@ -374,6 +755,86 @@ where
                 let input = *input.get_unchecked(idx);
                 ptr::write(data.as_mut_ptr().cast::<B>().add(i), input);
             }
+            Ok(data.assume_init())
+        }
+    }
+
+    /// Loads the vector from an array using indices known at compile time.
+    ///
+    /// This is like [`gather_load`][Vector::gather_load], but the indices are provided by a
+    /// [`ConstIndices`] type. As they are known at compile time, the bounds are checked during
+    /// compilation too and the compiler can turn the operation into a fixed shuffle.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use slipstream::vector::ConstIndices;
+    ///
+    /// struct Reverse;
+    ///
+    /// impl ConstIndices<4> for Reverse {
+    ///     const IDX: [usize; 4] = [3, 2, 1, 0];
+    /// }
+    ///
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// assert_eq!(u32x4::gather_const::<Reverse, 4>(&v), u32x4::new([4, 3, 2, 1]));
+    /// ```
+    #[inline]
+    pub fn gather_const<I, const L: usize>(input: &[B; L]) -> Self
+    where
+        I: ConstIndices<S>,
+    {
+        #[allow(clippy::let_unit_value)]
+        let () = IndicesCheck::<I, S, L>::IN_BOUNDS;
+        let mut data = MaybeUninit::<Self>::uninit();
+        unsafe {
+            for i in 0..S {
+                ptr::write(data.as_mut_ptr().cast::<B>().add(i), input[I::IDX[i]]);
+            }
+            data.assume_init()
+        }
+    }
+
+    /// Loads lanes from a slice at regular distance from each other.
+    ///
+    /// The lane `i` is loaded from the index `start + STRIDE * i`. This is the same as
+    /// [`gather_load`] with these indices, but as the stride is known at compile time, the
+    /// compiler can generate better code (eg. use shuffles for small strides).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let rgba = [
+    ///     10, 11, 12, 13,
+    ///     20, 21, 22, 23,
+    ///     30, 31, 32, 33,
+    ///     40, 41, 42, 43,
+    /// ];
+    /// let green = u8x4::gather_strided::<4>(&rgba, 1);
+    /// assert_eq!(green, u8x4::new([11, 21, 31, 41]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If any of the loaded positions is out of bounds of the `input`.
+    ///
+    /// [`gather_load`]: Vector::gather_load
+    #[inline]
+    pub fn gather_strided<const STRIDE: usize>(input: &[B], start: usize) -> Self {
+        Self::assert_size();
+        let last = STRIDE
+            .checked_mul(S - 1)
+            .and_then(|offset| offset.checked_add(start));
+        assert!(
+            matches!(last, Some(last) if last < input.len()),
+            "Gather out of bounds"
+        );
+        let mut data = MaybeUninit::<Self>::uninit();
+        unsafe {
+            for i in 0..S {
+                let input = *input.get_unchecked(start + STRIDE * i);
+                ptr::write(data.as_mut_ptr().cast::<B>().add(i), input);
+            }
             data.assume_init()
         }
     }
@@ -430,11 +891,66 @@ where
         let len = idx.len();
         assert_eq!(S, len, "Gathering vector from wrong number of indexes");
         assert_eq!(S, mask.len(), "Gathering with wrong sized mask");
+        // Check prior to loading anything, same as with scatter_store_masked.
+        let in_bounds = idx
+            .iter()
+            .enumerate()
+            .all(|(i, &l)| !mask[i].bool() || l < input.len());
+        assert!(in_bounds, "Gather out of bounds");
         for i in 0..S {
             unsafe {
                 if mask.get_unchecked(i).bool() {
+                    // get_unchecked: index checked above in bulk.
                     let idx = *idx.get_unchecked(i);
-                    self[i] = input[idx];
+                    self[i] = *input.get_unchecked(idx);
+                }
+            }
+        }
+        self
+    }
+
+    /// A masked version of [`new`].
+    ///
+    /// Loads the enabled lanes from the corresponding elements of the continuous `input` slice
+    /// (lane `i` is loaded from `input[i]`). The disabled lanes are kept from `self`.
+    ///
+    /// Unlike [`new`], the `input` doesn't have to be of the exact length. It only needs to
+    /// contain the elements for the enabled lanes, which makes it suitable for loading the odd
+    /// end of a slice. This is usually faster than going through
+    /// [`gather_load_masked`][Vector::gather_load_masked].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let tail = [1, 2, 3];
+    /// let v = u32x4::splat(42).load_masked(&tail, [true, true, true, false]);
+    /// assert_eq!(v, u32x4::new([1, 2, 3, 42]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * If the `mask` has a different length than the vector.
+    /// * If any of the enabled lanes is out of bounds of `input`.
+    ///
+    /// [`new`]: Vector::new
+    #[inline]
+    pub fn load_masked<I, M, MB>(mut self, input: I, mask: M) -> Self
+    where
+        I: AsRef<[B]>,
+        M: AsRef<[MB]>,
+        MB: Mask,
+    {
+        let input = input.as_ref();
+        let mask = mask.as_ref();
+        assert_eq!(S, mask.len(), "Loading vector with wrong sized mask");
+        let in_bounds = (input.len()..S).all(|i| !mask[i].bool());
+        assert!(in_bounds, "Load out of bounds");
+        for i in 0..S {
+            if mask[i].bool() {
+                unsafe {
+                    // get_unchecked: checked above in bulk.
+                    self[i] = *input.get_unchecked(i);
                 }
             }
         }
@@ -456,34 +972,146 @@ where
         output.as_mut().copy_from_slice(&self[..])
     }
 
-    /// Store the vector into a slice by indexing it.
+    /// Loads the vector using a volatile read.
     ///
-    /// This is the inverse of [`gather_load`][Vector::gather_load]. It takes the lanes of the
-    /// vector and stores them into the slice into given indices.
+    /// The whole vector is read at once, using [`ptr::read_volatile`]. This is meant for memory
+    /// that can change outside of the program's control, like memory-mapped devices or DMA
+    /// buffers. The compiler is not allowed to elide or reorder the read with other volatile
+    /// accesses.
     ///
-    /// If you want to store it into a continuous slice, it is potentially faster to do it using
-    /// the `copy_from_slice` method or by [`store`][Vector::store]:
+    /// # Safety
     ///
-    /// ```rust
-    /// # use slipstream::prelude::*;
-    /// let mut data = vec![0; 6];
-    /// let v = u32x4::new([1, 2, 3, 4]);
-    /// data[0..4].copy_from_slice(&v[..]);
-    /// assert_eq!(&data[..], &[1, 2, 3, 4, 0, 0]);
-    /// v.store(&mut data[..4]);
-    /// assert_eq!(&data[..], &[1, 2, 3, 4, 0, 0]);
-    /// ```
+    /// The pointer must be valid for reading `Self::LANES` consecutive elements and must be
+    /// aligned at least to the alignment of the base type `B` (not necessarily to the alignment of
+    /// the vector).
     ///
     /// # Examples
     ///
     /// ```rust
     /// # use slipstream::prelude::*;
-    /// let mut data = vec![0; 6];
-    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// let buffer = [1u32, 2, 3, 4];
+    /// let v = unsafe { u32x4::load_volatile(buffer.as_ptr()) };
+    /// assert_eq!(v, u32x4::new([1, 2, 3, 4]));
+    /// ```
+    #[inline]
+    pub unsafe fn load_volatile(input: *const B) -> Self {
+        Self::assert_size();
+        Self {
+            _align: [],
+            data: ptr::read_volatile(input.cast()),
+        }
+    }
+
+    /// Stores the vector using a volatile write.
+    ///
+    /// This is the counterpart of [`load_volatile`][Vector::load_volatile], writing the whole
+    /// vector at once using [`ptr::write_volatile`].
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for writing `Self::LANES` consecutive elements and must be
+    /// aligned at least to the alignment of the base type `B`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let mut buffer = [0u32; 4];
+    /// unsafe { u32x4::new([1, 2, 3, 4]).store_volatile(buffer.as_mut_ptr()) };
+    /// assert_eq!(buffer, [1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub unsafe fn store_volatile(self, output: *mut B) {
+        ptr::write_volatile(output.cast(), self.data);
+    }
+
+    /// A masked version of [`store`].
+    ///
+    /// Stores the enabled lanes into the corresponding elements of the continuous `output`
+    /// slice (lane `i` goes to `output[i]`). The elements corresponding to the disabled lanes are
+    /// left untouched.
+    ///
+    /// Unlike [`store`], the `output` doesn't have to be of the exact length. It only needs to
+    /// contain the elements the enabled lanes are stored into, which allows storing the odd end
+    /// of a slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let mut data = [0; 3];
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// v.store_masked(&mut data, [true, false, true, false]);
+    /// assert_eq!(data, [1, 0, 3]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// * If the `mask` has a different length than the vector.
+    /// * If any of the enabled lanes is out of bounds of `output`.
+    ///
+    /// [`store`]: Vector::store
+    #[inline]
+    pub fn store_masked<O, M, MB>(self, mut output: O, mask: M)
+    where
+        O: AsMut<[B]>,
+        M: AsRef<[MB]>,
+        MB: Mask,
+    {
+        let output = output.as_mut();
+        let mask = mask.as_ref();
+        assert_eq!(S, mask.len(), "Storing vector with wrong sized mask");
+        // Check prior to writing anything, we don't want to do partial store.
+        let in_bounds = (output.len()..S).all(|i| !mask[i].bool());
+        assert!(in_bounds, "Store out of bounds");
+        for i in 0..S {
+            if mask[i].bool() {
+                unsafe {
+                    // get_unchecked: checked above in bulk.
+                    *output.get_unchecked_mut(i) = self[i];
+                }
+            }
+        }
+    }
+
+    /// Store the vector into a slice by indexing it.
+    ///
+    /// This is the inverse of [`gather_load`][Vector::gather_load]. It takes the lanes of the
+    /// vector and stores them into the slice into given indices.
+    ///
+    /// If you want to store it into a continuous slice, it is potentially faster to do it using
+    /// the `copy_from_slice` method or by [`store`][Vector::store]:
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let mut data = vec![0; 6];
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// data[0..4].copy_from_slice(&v[..]);
+    /// assert_eq!(&data[..], &[1, 2, 3, 4, 0, 0]);
+    /// v.store(&mut data[..4]);
+    /// assert_eq!(&data[..], &[1, 2, 3, 4, 0, 0]);
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let mut data = vec![0; 6];
+    /// let v = u32x4::new([1, 2, 3, 4]);
     /// v.scatter_store(&mut data, [2, 5, 0, 1]);
     /// assert_eq!(&data[..], &[3, 4, 1, 0, 0, 2]);
     /// ```
     ///
+    /// The indices can also be computed in a vector:
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let mut data = vec![0; 8];
+    /// let idx = usizex4::new([0, 1, 2, 3]) * usizex4::splat(2);
+    /// u32x4::new([1, 2, 3, 4]).scatter_store(&mut data, idx);
+    /// assert_eq!(&data[..], &[1, 0, 2, 0, 3, 0, 4, 0]);
+    /// ```
+    ///
     /// # Warning
     ///
     /// If multiple lanes are to be stored into the same slice element, it is not specified which
@@ -598,396 +1226,3879 @@ where
         }
     }
 
-    /// A lane-wise maximum.
+    /// Blend self and other using a bitmask.
     ///
-    /// # Examples
+    /// Like [`blend`][Vector::blend], but the mask is packed in an integer (as produced by
+    /// [`to_bitmask`][Vector::to_bitmask]). If the bit `i` is set, the lane `i` is taken from
+    /// `other`, otherwise from `self`. The vector can have at most 32 lanes (checked at compile
+    /// time) and the higher bits are ignored.
     ///
     /// ```rust
     /// # use slipstream::prelude::*;
-    /// let a = u32x4::new([1, 4, 2, 5]);
-    /// let b = u32x4::new([2, 3, 2, 6]);
-    /// assert_eq!(a.maximum(b), u32x4::new([2, 4, 2, 6]));
+    /// let odd = u32x4::new([1, 3, 5, 7]);
+    /// let even = u32x4::new([2, 4, 6, 8]);
+    /// assert_eq!(odd.blend_bitmask(even, 0b1010), u32x4::new([1, 4, 5, 8]));
     /// ```
     #[inline]
-    pub fn maximum(self, other: Self) -> Self
-    where
-        B: PartialOrd,
-    {
-        let m = self.lt(other);
-        self.blend(other, m)
+    pub fn blend_bitmask(self, other: Self, bits: u32) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = BitmaskCheck::<S>::FITS;
+        let mut result = self;
+        for (i, (res, o)) in result.data.iter_mut().zip(other.data.iter()).enumerate() {
+            if bits & (1 << i) != 0 {
+                *res = *o;
+            }
+        }
+        result
     }
 
-    /// A lane-wise maximum.
+    /// Negates the lanes enabled by the mask.
     ///
-    /// # Examples
+    /// This is the same as `self.blend(-self, mask)`, which is a common step in butterflies of
+    /// transforms (eg. applying the twiddle factors of FFT) or sign flips in general.
     ///
     /// ```rust
     /// # use slipstream::prelude::*;
-    /// let a = u32x4::new([1, 4, 2, 5]);
-    /// let b = u32x4::new([2, 3, 2, 6]);
-    /// assert_eq!(a.minimum(b), u32x4::new([1, 3, 2, 5]));
+    /// let v = f32x4::new([1.0, 2.0, 3.0, 4.0]);
+    /// let mask = m32x4::new([m32::FALSE, m32::TRUE, m32::FALSE, m32::TRUE]);
+    /// assert_eq!(v.neg_masked(mask), f32x4::new([1.0, -2.0, 3.0, -4.0]));
     /// ```
-    #[inline]
-    pub fn minimum(self, other: Self) -> Self
-    where
-        B: PartialOrd,
-    {
-        let m = self.gt(other);
-        self.blend(other, m)
-    }
-
-    // TODO: Example
-    /// Sums the lanes together.
     ///
-    /// The additions are done in a tree manner: `(a[0] + a[1]) + (a[2] + a[3])`.
+    /// # Panics
     ///
-    /// Note that this is potentially a slow operation. Prefer to do as many operations on whole
-    /// vectors and only at the very end perform the horizontal operation.
+    /// If the mask has a different length than the vector.
     #[inline]
-    pub fn horizontal_sum(self) -> B
+    pub fn neg_masked<M, MB>(self, mask: M) -> Self
     where
-        B: Add<Output = B>,
+        B: Neg<Output = B>,
+        M: AsRef<[MB]>,
+        MB: Mask,
     {
-        #[inline(always)]
-        fn inner<B: Copy + Add<Output = B>>(d: &[B]) -> B {
-            if d.len() == 1 {
-                d[0]
-            } else {
-                let mid = d.len() / 2;
-                inner(&d[..mid]) + inner(&d[mid..])
+        let mask = mask.as_ref();
+        assert_eq!(S, mask.len(), "Negating vector with wrong sized mask");
+        let mut result = self;
+        for (res, m) in result.data.iter_mut().zip(mask) {
+            if m.bool() {
+                *res = -*res;
             }
         }
-        inner(&self.data)
+        result
     }
 
-    /// Multiplies all the lanes of the vector.
+    /// Exchanges two lanes.
     ///
-    /// The multiplications are done in a tree manner: `(a[0] * a[1]) * (a[2] * a[3])`.
+    /// Returns a copy of the vector with lanes `i` and `j` swapped. This is useful for small
+    /// permutations, like reordering components of a quaternion.
     ///
-    /// Note that this is potentially a slow operation. Prefer to do as many operations on whole
-    /// vectors and only at the very end perform the horizontal operation.
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// assert_eq!(v.swap_lanes(0, 3), u32x4::new([4, 2, 3, 1]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If any of the indices is out of range.
     #[inline]
-    pub fn horizontal_product(self) -> B
-    where
-        B: Mul<Output = B>,
-    {
-        #[inline(always)]
-        fn inner<B: Copy + Mul<Output = B>>(d: &[B]) -> B {
-            if d.len() == 1 {
-                d[0]
-            } else {
-                let mid = d.len() / 2;
-                inner(&d[..mid]) * inner(&d[mid..])
-            }
-        }
-        inner(&self.data)
+    pub fn swap_lanes(mut self, i: usize, j: usize) -> Self {
+        assert!(
+            i < S && j < S,
+            "Swapping lanes {} and {} out of {}",
+            i,
+            j,
+            S
+        );
+        self.data.swap(i, j);
+        self
     }
 
-    cmp_op!(
-        /// Lane-wise `==`.
-        PartialEq => eq;
-
-        /// Lane-wise `<`.
-        PartialOrd => lt;
-
-        /// Lane-wise `>`.
-        PartialOrd => gt;
-
-        /// Lane-wise `<=`.
-        PartialOrd => le;
-
-        /// Lane-wise `>=`.
-        PartialOrd => ge;
-    );
-}
-
-impl<A, B, const S: usize> Vector<A, B, S>
-where
-    A: Align,
-    B: Repr + Float,
-{
-    /// Fused multiply-add. Computes (self * a) + b with only one rounding
-    /// error, yielding a more accurate result than an unfused multiply-add.
+    /// Rotates the lanes towards the beginning of the vector.
     ///
-    /// Using mul_add can be more performant than an unfused multiply-add if the
-    /// target architecture has a dedicated fma CPU instruction.
+    /// The lane `i` of the result is the lane `i + n` of `self` (wrapping around), so the first
+    /// `n` lanes move to the end. The `n` is taken modulo the number of lanes.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// assert_eq!(v.rotate_lanes_left(1), u32x4::new([2, 3, 4, 1]));
+    /// ```
     #[inline]
-    pub fn mul_add(self, a: Self, b: Self) -> Self {
-        let mut result = Self::splat(B::zero());
-        for ((res, &s), (&a, &b)) in result
-            .data
-            .iter_mut()
-            .zip(self.data.iter())
-            .zip(a.data.iter().zip(b.data.iter()))
-        {
-            *res = s.mul_add(a, b);
+    pub fn rotate_lanes_left(self, n: usize) -> Self {
+        let n = n % S;
+        let mut idx = [0; S];
+        for (i, idx) in idx.iter_mut().enumerate() {
+            *idx = (i + n) % S;
         }
-        result
+        Self::gather_load(self, idx)
     }
-}
 
-impl<A: Align, B: Repr, const S: usize> Masked for Vector<A, B, S> {
-    type Mask = Vector<A, B::Mask, S>;
-}
-
-impl<A: Align, B: Default + Repr, const S: usize> Default for Vector<A, B, S> {
+    /// Rotates the lanes towards the end of the vector.
+    ///
+    /// The lane `i + n` of the result is the lane `i` of `self` (wrapping around), so the last
+    /// `n` lanes move to the beginning. The `n` is taken modulo the number of lanes.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// assert_eq!(v.rotate_lanes_right(1), u32x4::new([4, 1, 2, 3]));
+    /// ```
     #[inline]
-    fn default() -> Self {
-        Self::splat(Default::default())
-    }
-}
-
-impl<A: Align, B: Debug + Repr, const S: usize> Debug for Vector<A, B, S> {
-    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
-        fmt.debug_tuple("Vector").field(&self.data).finish()
+    pub fn rotate_lanes_right(self, n: usize) -> Self {
+        self.rotate_lanes_left(S - n % S)
     }
-}
 
-impl<A: Align, B: Repr, const S: usize> Deref for Vector<A, B, S> {
-    type Target = [B; S];
+    /// Shifts the lanes towards the beginning of the vector, filling the end.
+    ///
+    /// The lane `i` of the result is the lane `i + n` of `self`, the last `n` lanes are set to
+    /// `fill`. Shifting by the number of lanes or more results in a vector of only `fill`.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// assert_eq!(v.shift_lanes_left(2, 0), u32x4::new([3, 4, 0, 0]));
+    /// ```
     #[inline]
-    fn deref(&self) -> &[B; S] {
-        &self.data
+    pub fn shift_lanes_left(self, n: usize, fill: B) -> Self {
+        let n = n.min(S);
+        let mut result = Self::splat(fill);
+        result.data[..S - n].copy_from_slice(&self.data[n..]);
+        result
     }
-}
 
-impl<A: Align, B: Repr, const S: usize> DerefMut for Vector<A, B, S> {
+    /// Shifts the lanes towards the end of the vector, filling the beginning.
+    ///
+    /// The lane `i + n` of the result is the lane `i` of `self`, the first `n` lanes are set to
+    /// `fill`. Shifting by the number of lanes or more results in a vector of only `fill`.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// assert_eq!(v.shift_lanes_right(1, 9), u32x4::new([9, 1, 2, 3]));
+    /// ```
     #[inline]
-    fn deref_mut(&mut self) -> &mut [B; S] {
-        &mut self.data
+    pub fn shift_lanes_right(self, n: usize, fill: B) -> Self {
+        let n = n.min(S);
+        let mut result = Self::splat(fill);
+        result.data[n..].copy_from_slice(&self.data[..S - n]);
+        result
     }
-}
 
-impl<A: Align, B: Repr, const S: usize> AsRef<[B]> for Vector<A, B, S> {
+    /// Packs the lanes enabled by the `mask` to the beginning of the vector.
+    ///
+    /// The enabled lanes are moved to the low end of the result, keeping their relative order.
+    /// The second returned value is the number of enabled lanes (and therefore the number of the
+    /// valid lanes in the result).
+    ///
+    /// The lanes of the result after this count are **unspecified**. They do hold some valid
+    /// values of the base type, but the caller should not rely on what these are.
+    ///
+    /// The inverse operation is [`expand`][Vector::expand].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([10, 20, 30, 40]);
+    /// let (packed, cnt) = v.compress([false, true, false, true]);
+    /// assert_eq!(cnt, 2);
+    /// assert_eq!(&packed[..cnt], &[20, 40]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the `mask` has a different length than the vector.
+    #[inline]
+    pub fn compress<M, MB>(self, mask: M) -> (Self, usize)
+    where
+        M: AsRef<[MB]>,
+        MB: Mask,
+    {
+        let mask = mask.as_ref();
+        assert_eq!(S, mask.len(), "Compressing vector with wrong sized mask");
+        let mut result = self;
+        let mut cnt = 0;
+        for i in 0..S {
+            if mask[i].bool() {
+                result[cnt] = self[i];
+                cnt += 1;
+            }
+        }
+        (result, cnt)
+    }
+
+    /// Spreads the beginning of `other` into the lanes enabled by the `mask`.
+    ///
+    /// This is the inverse of [`compress`][Vector::compress]. The consecutive lanes from the
+    /// beginning of `other` are placed into the enabled lanes (in order). The disabled lanes are
+    /// kept from `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let packed = u32x4::new([20, 40, 0, 0]);
+    /// let v = u32x4::splat(1).expand(packed, [false, true, false, true]);
+    /// assert_eq!(v, u32x4::new([1, 20, 1, 40]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the `mask` has a different length than the vector.
+    #[inline]
+    pub fn expand<M, MB>(mut self, other: Self, mask: M) -> Self
+    where
+        M: AsRef<[MB]>,
+        MB: Mask,
+    {
+        let mask = mask.as_ref();
+        assert_eq!(S, mask.len(), "Expanding vector with wrong sized mask");
+        let mut src = 0;
+        for i in 0..S {
+            if mask[i].bool() {
+                self[i] = other[src];
+                src += 1;
+            }
+        }
+        self
+    }
+
+    /// Copies `N` consecutive lanes, starting at `start`, into a narrower vector.
+    ///
+    /// The alignment of the result is chosen by the type of the result, so usually a type hint is
+    /// needed.
+    ///
+    /// (This is not called `sub`, as that would shadow the [`Sub`] operator method.)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x8::new([0, 1, 2, 3, 4, 5, 6, 7]);
+    /// let mid: u32x2 = v.sub_vector(3);
+    /// assert_eq!(mid, u32x2::new([3, 4]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the requested lanes are out of range of this vector.
+    #[inline]
+    pub fn sub_vector<A2: Align, const N: usize>(self, start: usize) -> Vector<A2, B, N> {
+        assert!(
+            start <= S && N <= S - start,
+            "Sub-vector out of range ({}..{} of {})",
+            start,
+            start.saturating_add(N),
+            S,
+        );
+        Vector::new(&self.data[start..start + N])
+    }
+
+    /// Splits the vector into an array of narrower vectors.
+    ///
+    /// The vector is split into `C` vectors of `N` lanes each, with the lanes in the same order as
+    /// in the original. The sizes must match exactly (`N * C == S`), which is checked at compile
+    /// time.
+    ///
+    /// (This is not called `chunks`, as that would shadow the `chunks` method of slices,
+    /// available through dereferencing.)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x16::splat(1.0);
+    /// let parts: [f32x4; 4] = v.split_chunks();
+    /// assert_eq!(parts, [f32x4::splat(1.0); 4]);
+    /// ```
+    ///
+    /// The sizes not matching is detected during compilation:
+    ///
+    /// ```rust,compile_fail
+    /// # use slipstream::prelude::*;
+    /// let parts: [u32x2; 3] = u32x8::default().split_chunks();
+    /// ```
+    #[inline]
+    pub fn split_chunks<A2: Align, const N: usize, const C: usize>(self) -> [Vector<A2, B, N>; C] {
+        #[allow(clippy::let_unit_value)]
+        let () = SizeCheck::<S, N, C>::CHUNKS;
+        let mut result = MaybeUninit::<[Vector<A2, B, N>; C]>::uninit();
+        unsafe {
+            for i in 0..C {
+                ptr::write(
+                    result.as_mut_ptr().cast::<Vector<A2, B, N>>().add(i),
+                    Vector::new(&self.data[i * N..(i + 1) * N]),
+                );
+            }
+            result.assume_init()
+        }
+    }
+
+    /// Creates a vector by repeating a narrower one.
+    ///
+    /// The lanes of the result are filled with the lanes of `small`, going around repeatedly
+    /// (lane `i` is set to `small[i % M]`). The size of the result must be divisible by the size
+    /// of `small`, which is checked at compile time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u8x8::repeat(u8x2::new([1, 2]));
+    /// assert_eq!(v, u8x8::new([1, 2, 1, 2, 1, 2, 1, 2]));
+    /// ```
+    #[inline]
+    pub fn repeat<A2: Align, const M: usize>(small: Vector<A2, B, M>) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = DivisibleCheck::<S, M>::DIVISIBLE;
+        let mut data = MaybeUninit::<Self>::uninit();
+        unsafe {
+            for i in 0..S {
+                ptr::write(data.as_mut_ptr().cast::<B>().add(i), small[i % M]);
+            }
+            data.assume_init()
+        }
+    }
+
+    /// Splits interleaved data into a vector for each field.
+    ///
+    /// The input is expected to contain `S` records of `F` fields each, stored one after another
+    /// (array of structures, eg. `[x0, y0, x1, y1, ...]`). The result contains a vector for each
+    /// field (structure of arrays, eg. `[[x0, x1, ...], [y0, y1, ...]]`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let points = [1.0, -1.0, 2.0, -2.0, 3.0, -3.0, 4.0, -4.0];
+    /// let [xs, ys] = f32x4::deinterleave::<2>(&points);
+    /// assert_eq!(xs, f32x4::new([1.0, 2.0, 3.0, 4.0]));
+    /// assert_eq!(ys, f32x4::new([-1.0, -2.0, -3.0, -4.0]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the length of the input is not `S * F`.
+    #[inline]
+    pub fn deinterleave<const F: usize>(input: &[B]) -> [Self; F] {
+        Self::assert_size();
+        assert_eq!(
+            input.len(),
+            S * F,
+            "Deinterleaving wrong sized slice (expected {}, got {})",
+            S * F,
+            input.len(),
+        );
+        let mut data = MaybeUninit::<[Self; F]>::uninit();
+        unsafe {
+            for i in 0..S {
+                for f in 0..F {
+                    ptr::write(
+                        data.as_mut_ptr().cast::<B>().add(f * S + i),
+                        *input.get_unchecked(i * F + f),
+                    );
+                }
+            }
+            data.assume_init()
+        }
+    }
+
+    masked_op!(
+        /// Adds `other` to the lanes enabled by the `mask`.
+        ///
+        /// The disabled lanes are kept from `self`. This is equivalent to `self.blend(self +
+        /// other, mask)`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # use slipstream::prelude::*;
+        /// let pos = f32x4::new([1.0, 2.0, 3.0, 4.0]);
+        /// let vel = f32x4::splat(0.5);
+        /// let active = [true, false, true, false];
+        /// assert_eq!(pos.add_masked(vel, active), f32x4::new([1.5, 2.0, 3.5, 4.0]));
+        /// ```
+        Add, add => add_masked;
+
+        /// Subtracts `other` from the lanes enabled by the `mask`.
+        ///
+        /// The disabled lanes are kept from `self`. This is equivalent to `self.blend(self -
+        /// other, mask)`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # use slipstream::prelude::*;
+        /// let a = u32x4::new([10, 20, 30, 40]);
+        /// let mask = m32x4::new([m32::FALSE, m32::TRUE, m32::FALSE, m32::TRUE]);
+        /// assert_eq!(a.sub_masked(u32x4::splat(5), mask), u32x4::new([10, 15, 30, 35]));
+        /// ```
+        Sub, sub => sub_masked;
+
+        /// Multiplies the lanes enabled by the `mask` by `other`.
+        ///
+        /// The disabled lanes are kept from `self`. This is equivalent to `self.blend(self *
+        /// other, mask)`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # use slipstream::prelude::*;
+        /// let a = u32x4::new([1, 2, 3, 4]);
+        /// let mask = m32x4::new([m32::TRUE, m32::TRUE, m32::FALSE, m32::FALSE]);
+        /// assert_eq!(a.mul_masked(u32x4::splat(3), mask), u32x4::new([3, 6, 3, 4]));
+        /// ```
+        Mul, mul => mul_masked;
+    );
+
+    /// A lane-wise maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = u32x4::new([1, 4, 2, 5]);
+    /// let b = u32x4::new([2, 3, 2, 6]);
+    /// assert_eq!(a.maximum(b), u32x4::new([2, 4, 2, 6]));
+    /// ```
+    #[inline]
+    pub fn maximum(self, other: Self) -> Self
+    where
+        B: PartialOrd,
+    {
+        let m = self.lt(other);
+        self.blend(other, m)
+    }
+
+    /// A lane-wise maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = u32x4::new([1, 4, 2, 5]);
+    /// let b = u32x4::new([2, 3, 2, 6]);
+    /// assert_eq!(a.minimum(b), u32x4::new([1, 3, 2, 5]));
+    /// ```
+    #[inline]
+    pub fn minimum(self, other: Self) -> Self
+    where
+        B: PartialOrd,
+    {
+        let m = self.gt(other);
+        self.blend(other, m)
+    }
+
+    /// Restricts all lanes to the `lo..=hi` interval.
+    ///
+    /// This is done through [`maximum`][Vector::maximum] and [`minimum`][Vector::minimum], so it
+    /// works for integer vectors without any conversions. It is handy for example to clamp
+    /// computed indices before a [`gather_load`][Vector::gather_load].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = i32x4::new([-3, 2, 7, 10]);
+    /// assert_eq!(v.clamp_scalar(0, 7), i32x4::new([0, 2, 7, 7]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `lo > hi` (or they are not comparable).
+    #[inline]
+    pub fn clamp_scalar(self, lo: B, hi: B) -> Self
+    where
+        B: PartialOrd,
+    {
+        assert!(lo <= hi, "Clamping with lower bound above the upper bound");
+        self.maximum(Self::splat(lo)).minimum(Self::splat(hi))
+    }
+
+    /// Picks one of three vectors per lane, depending on how `self` compares to `other`.
+    ///
+    /// Lanes where `self < other` are taken from `lt`, where `self > other` from `gt` and the rest
+    /// from `eq`. Note that the rest includes lanes that are unordered (eg. `NaN`s).
+    ///
+    /// This is a shortcut for a comparison followed by two [`blend`][Vector::blend]s.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = i32x4::new([-5, 0, 3, 0]);
+    /// let signum = v.select_cmp(i32x4::splat(0), i32x4::splat(-1), i32x4::splat(0), i32x4::splat(1));
+    /// assert_eq!(signum, i32x4::new([-1, 0, 1, 0]));
+    /// ```
+    #[inline]
+    pub fn select_cmp(self, other: Self, lt: Self, eq: Self, gt: Self) -> Self
+    where
+        B: PartialOrd,
+    {
+        let lt_mask = self.lt(other);
+        let gt_mask = self.gt(other);
+        eq.blend(lt, lt_mask).blend(gt, gt_mask)
+    }
+
+    // One layer of a sorting network. All the comparators in the layer are independent, so it
+    // can be done on the whole vector at once.
+    #[inline(always)]
+    fn compare_exchange<F>(self, pairs: &[(usize, usize)], less: &F) -> Self
+    where
+        F: Fn(&B, &B) -> bool,
+    {
+        let mut partner = [0; S];
+        let mut upper = [false; S];
+        for (i, p) in partner.iter_mut().enumerate() {
+            *p = i;
+        }
+        for &(lo, hi) in pairs {
+            partner[lo] = hi;
+            partner[hi] = lo;
+            upper[hi] = true;
+        }
+        let other = Self::gather_load(self, partner);
+        let mut swap = [false; S];
+        for i in 0..S {
+            swap[i] = if upper[i] {
+                less(&self[i], &other[i])
+            } else {
+                less(&other[i], &self[i])
+            };
+        }
+        self.blend(other, swap)
+    }
+
+    #[inline]
+    fn sort_network_by<F>(mut self, less: F) -> Self
+    where
+        F: Fn(&B, &B) -> bool,
+    {
+        let network = match S {
+            2 => SORT_2,
+            4 => SORT_4,
+            8 => SORT_8,
+            16 => SORT_16,
+            _ => {
+                // Odd-even transposition sort. Still a sorting network, but a slow one.
+                for round in 0..S {
+                    for i in (round % 2..S - 1).step_by(2) {
+                        if less(&self[i + 1], &self[i]) {
+                            self.data.swap(i, i + 1);
+                        }
+                    }
+                }
+                return self;
+            }
+        };
+        for layer in network {
+            self = self.compare_exchange(layer, &less);
+        }
+        self
+    }
+
+    /// Sorts the lanes in ascending order.
+    ///
+    /// This is implemented as a sorting network ‒ a fixed sequence of compare-and-exchange steps.
+    /// For 2, 4, 8 and 16 lanes, each step compares pairs of lanes across the whole vector and
+    /// blends the results. The networks are optimal for vectors of up to 8 lanes and reasonably
+    /// good ones are used for 16 lanes. Other sizes fall back to a slower odd-even transposition
+    /// sort, comparing one pair of lanes at a time. It is meant for sorting the lanes of a single
+    /// small vector (eg. for median filters), not as a general-purpose sort.
+    ///
+    /// If some lanes are not comparable (eg. `NaN`s), the order of the result is unspecified, but
+    /// it still contains all the original lanes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = i32x4::new([3, -1, 4, 1]);
+    /// assert_eq!(v.sort_lanes(), i32x4::new([-1, 1, 3, 4]));
+    /// ```
+    #[inline]
+    pub fn sort_lanes(self) -> Self
+    where
+        B: PartialOrd,
+    {
+        self.sort_network_by(|a, b| a < b)
+    }
+
+    /// Sorts the lanes by a comparator.
+    ///
+    /// This is the same sorting network as in [`sort_lanes`][Vector::sort_lanes], but the
+    /// comparisons are done by the provided function. This allows sorting of floats in a
+    /// deterministic way, even with `NaN`s, by passing the `total_cmp` method.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x4::new([2.0, f32::NAN, -1.0, 0.5]);
+    /// let sorted = v.sort_lanes_by(f32::total_cmp);
+    /// assert_eq!(&sorted[..3], &[-1.0, 0.5, 2.0]);
+    /// assert!(sorted[3].is_nan());
+    /// ```
+    #[inline]
+    pub fn sort_lanes_by<F>(self, cmp: F) -> Self
+    where
+        F: Fn(&B, &B) -> Ordering,
+    {
+        self.sort_network_by(|a, b| cmp(a, b) == Ordering::Less)
+    }
+
+    /// Checks if the lanes are sorted in ascending order.
+    ///
+    /// Each lane is compared with the next one. If some lanes are not comparable (eg. `NaN`s),
+    /// the vector is not sorted.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = i32x4::new([3, -1, 4, 1]);
+    /// assert!(!v.is_sorted());
+    /// assert!(v.sort_lanes().is_sorted());
+    /// ```
+    #[inline]
+    pub fn is_sorted(self) -> bool
+    where
+        B: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Checks if the lanes are sorted according to a comparator.
+    ///
+    /// The `in_order` is called with pairs of neighbouring lanes and returns if they are in the
+    /// right order.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = i32x4::new([4, 3, 3, 1]);
+    /// assert!(v.is_sorted_by(|a, b| a >= b));
+    /// ```
+    #[inline]
+    pub fn is_sorted_by<F>(self, mut in_order: F) -> bool
+    where
+        F: FnMut(&B, &B) -> bool,
+    {
+        self.data.windows(2).all(|w| in_order(&w[0], &w[1]))
+    }
+
+    /// Returns the median of the lanes.
+    ///
+    /// For odd number of lanes, this is the middle value. For even number of lanes, there are
+    /// two middle values. Floating point vectors return their average, integer vectors return the
+    /// lower of the two (so the result is always one of the lanes and there's no rounding).
+    ///
+    /// This is built on [`sort_lanes`][Vector::sort_lanes].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// assert_eq!(f32x4::new([4.0, 1.0, 3.0, 2.0]).median(), 2.5);
+    /// assert_eq!(u32x4::new([4, 1, 3, 2]).median(), 2);
+    /// ```
+    #[inline]
+    pub fn median(self) -> B
+    where
+        B: Median,
+    {
+        let sorted = self.sort_lanes();
+        if S % 2 == 1 {
+            sorted[S / 2]
+        } else {
+            B::middle(sorted[S / 2 - 1], sorted[S / 2])
+        }
+    }
+
+    /// Returns the `k`-th smallest lane (counting from zero).
+    ///
+    /// This is the order statistic used for rank filters ‒ `0` is the minimum, `S - 1` the maximum
+    /// and the middle ones give percentiles.
+    ///
+    /// The lanes are fully sorted by the same sorting network as in
+    /// [`sort_lanes`][Vector::sort_lanes] and the `k`-th is picked. Therefore, the cost doesn't
+    /// depend on `k` ‒ it's a fixed number of vector compare-and-exchange steps (`O(log² S)` of
+    /// them for the power-of-two sizes). If some lanes are not comparable (eg. `NaN`s), the result
+    /// is unspecified (but is one of the lanes).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u8x8::new([50, 10, 40, 20, 80, 30, 70, 60]);
+    /// assert_eq!(v.select_nth_lane(1), 20);
+    /// assert_eq!(v.select_nth_lane(7), 80);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `k` is not smaller than the number of lanes.
+    #[inline]
+    pub fn select_nth_lane(self, k: usize) -> B
+    where
+        B: PartialOrd,
+    {
+        assert!(k < S, "Selecting lane {} out of {}", k, S);
+        self.sort_lanes()[k]
+    }
+
+    // TODO: Example
+    /// Sums the lanes together.
+    ///
+    /// The additions are done in a tree manner: `(a[0] + a[1]) + (a[2] + a[3])`. If a strict
+    /// left-to-right order is needed, use
+    /// [`horizontal_sum_ordered`][Vector::horizontal_sum_ordered].
+    ///
+    /// Note that this is potentially a slow operation. Prefer to do as many operations on whole
+    /// vectors and only at the very end perform the horizontal operation.
+    #[inline]
+    pub fn horizontal_sum(self) -> B
+    where
+        B: Add<Output = B>,
+    {
+        #[inline(always)]
+        fn inner<B: Copy + Add<Output = B>>(d: &[B]) -> B {
+            if d.len() == 1 {
+                d[0]
+            } else {
+                let mid = d.len() / 2;
+                inner(&d[..mid]) + inner(&d[mid..])
+            }
+        }
+        inner(&self.data)
+    }
+
+    /// Sums the lanes together, strictly in the order of the lanes.
+    ///
+    /// The additions are done as `((a[0] + a[1]) + a[2]) + a[3]`. For floats, where the addition
+    /// is not associative, this gives the same bits as a plain scalar loop over the lanes,
+    /// independent of the optimizations. This is at the cost of speed, since the additions can't
+    /// run in parallel, so [`horizontal_sum`][Vector::horizontal_sum] should be preferred unless
+    /// the exact reproducibility is needed.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x4::new([1e8, 1.0, -1e8, 1.0]);
+    /// assert_eq!(v.horizontal_sum(), 0.0);
+    /// assert_eq!(v.horizontal_sum_ordered(), 1.0);
+    /// ```
+    #[inline]
+    pub fn horizontal_sum_ordered(self) -> B
+    where
+        B: Add<Output = B>,
+    {
+        self.data[1..]
+            .iter()
+            .fold(self.data[0], |acc, &lane| acc + lane)
+    }
+
+    /// Sums only the lanes enabled by the mask.
+    ///
+    /// The disabled lanes are replaced by zeroes and the result is summed the same way as in
+    /// [`horizontal_sum`][Vector::horizontal_sum]. If no lane is enabled, the result is zero.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x4::new([1.0, 2.0, 3.0, 4.0]);
+    /// let mask = v.gt(f32x4::splat(2.5));
+    /// assert_eq!(v.masked_horizontal_sum(mask), 7.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the mask has a different length than the vector.
+    #[inline]
+    pub fn masked_horizontal_sum<M, MB>(self, mask: M) -> B
+    where
+        B: Add<Output = B>,
+        M: AsRef<[MB]>,
+        MB: Mask,
+    {
+        let mask = mask.as_ref();
+        assert_eq!(S, mask.len(), "Summing vector with wrong sized mask");
+        Self::ZERO.blend(self, mask).horizontal_sum()
+    }
+
+    /// The outer product of two vectors.
+    ///
+    /// The result is a matrix (as an array of rows), where the row `i` is `other` multiplied by
+    /// the lane `i` of `self`. This is the building block of rank-1 updates.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = f32x2::new([1.0, 2.0]);
+    /// let b = f32x2::new([3.0, 4.0]);
+    /// assert_eq!(a.outer(b), [f32x2::new([3.0, 4.0]), f32x2::new([6.0, 8.0])]);
+    /// ```
+    #[inline]
+    pub fn outer(self, other: Self) -> [Self; S]
+    where
+        B: Mul<Output = B>,
+    {
+        let mut result = [other; S];
+        for (row, &lane) in result.iter_mut().zip(self.iter()) {
+            *row = Self::splat(lane) * other;
+        }
+        result
+    }
+
+    /// Multiplies all the lanes of the vector.
+    ///
+    /// The multiplications are done in a tree manner: `(a[0] * a[1]) * (a[2] * a[3])`.
+    ///
+    /// Note that this is potentially a slow operation. Prefer to do as many operations on whole
+    /// vectors and only at the very end perform the horizontal operation.
+    #[inline]
+    pub fn horizontal_product(self) -> B
+    where
+        B: Mul<Output = B>,
+    {
+        #[inline(always)]
+        fn inner<B: Copy + Mul<Output = B>>(d: &[B]) -> B {
+            if d.len() == 1 {
+                d[0]
+            } else {
+                let mid = d.len() / 2;
+                inner(&d[..mid]) * inner(&d[mid..])
+            }
+        }
+        inner(&self.data)
+    }
+
+    /// Folds the lanes into an accumulator, from the first lane to the last.
+    ///
+    /// Unlike [`horizontal_sum`][Vector::horizontal_sum], the accumulator can be of a different
+    /// type than the lanes and the order of the operations is fixed, so it can be used for
+    /// accumulations that are not associative (like hashing).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u8x4::new([1, 2, 3, 4]);
+    /// let s = v.fold(String::new(), |mut acc, lane| {
+    ///     acc.push_str(&lane.to_string());
+    ///     acc
+    /// });
+    /// assert_eq!(s, "1234");
+    /// ```
+    #[inline]
+    pub fn fold<Acc, F>(self, init: Acc, f: F) -> Acc
+    where
+        F: FnMut(Acc, B) -> Acc,
+    {
+        self.data.iter().copied().fold(init, f)
+    }
+
+    /// Iterates over the lanes together with their indices.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u8x4::new([10, 20, 30, 40]);
+    /// for (i, lane) in v.enumerate_lanes() {
+    ///     println!("Lane {}: {}", i, lane);
+    ///     assert_eq!(usize::from(lane), (i + 1) * 10);
+    /// }
+    /// ```
+    #[inline]
+    pub fn enumerate_lanes(self) -> impl Iterator<Item = (usize, B)> {
+        IntoIterator::into_iter(self.data).enumerate()
+    }
+
+    cmp_op!(
+        /// Lane-wise `==`.
+        PartialEq => eq;
+
+        /// Lane-wise `!=`.
+        PartialEq => ne;
+
+        /// Lane-wise `<`.
+        PartialOrd => lt;
+
+        /// Lane-wise `>`.
+        PartialOrd => gt;
+
+        /// Lane-wise `<=`.
+        PartialOrd => le;
+
+        /// Lane-wise `>=`.
+        PartialOrd => ge;
+    );
+
+    cmp_mask_op!(
+        /// Lane-wise `==`, packed into a bitmask.
+        ///
+        /// The bit `i` of the result is set if the comparison holds for lane `i`. This is the same
+        /// as `self.eq(other).to_bitmask()`, without going through the mask vector. The vector
+        /// can have at most 32 lanes (checked at compile time).
+        ///
+        /// ```rust
+        /// # use slipstream::prelude::*;
+        /// let a = u32x4::new([1, 2, 3, 4]);
+        /// assert_eq!(a.cmp_eq_mask(u32x4::new([1, 0, 3, 0])), 0b0101);
+        /// ```
+        PartialEq => eq, cmp_eq_mask;
+
+        /// Lane-wise `!=`, packed into a bitmask.
+        ///
+        /// See [`cmp_eq_mask`][Vector::cmp_eq_mask].
+        PartialEq => ne, cmp_ne_mask;
+
+        /// Lane-wise `<`, packed into a bitmask.
+        ///
+        /// This is the usual way to branch on a comparison:
+        ///
+        /// ```rust
+        /// # use slipstream::prelude::*;
+        /// let a = u32x4::new([1, 2, 3, 4]);
+        /// let limit = u32x4::splat(3);
+        /// if a.cmp_lt_mask(limit) != 0 {
+        ///     // Some lanes are below the limit
+        /// }
+        /// assert_eq!(a.cmp_lt_mask(limit), 0b0011);
+        /// ```
+        ///
+        /// See [`cmp_eq_mask`][Vector::cmp_eq_mask].
+        PartialOrd => lt, cmp_lt_mask;
+
+        /// Lane-wise `>`, packed into a bitmask.
+        ///
+        /// See [`cmp_eq_mask`][Vector::cmp_eq_mask].
+        PartialOrd => gt, cmp_gt_mask;
+
+        /// Lane-wise `<=`, packed into a bitmask.
+        ///
+        /// See [`cmp_eq_mask`][Vector::cmp_eq_mask].
+        PartialOrd => le, cmp_le_mask;
+
+        /// Lane-wise `>=`, packed into a bitmask.
+        ///
+        /// See [`cmp_eq_mask`][Vector::cmp_eq_mask].
+        PartialOrd => ge, cmp_ge_mask;
+    );
+
+    /// Lane-wise test for zero.
+    ///
+    /// The zero is the [`Default`] value of the base type.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([0, 5, 0, 1]);
+    /// assert_eq!(v.is_zero(), m32x4::new([m32::TRUE, m32::FALSE, m32::TRUE, m32::FALSE]));
+    /// ```
+    #[inline]
+    pub fn is_zero(self) -> <Self as Masked>::Mask
+    where
+        B: PartialEq + Default,
+    {
+        self.eq(Self::default())
+    }
+
+    /// Lane-wise test for non-zero values.
+    ///
+    /// This is the negation of [`is_zero`][Vector::is_zero].
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([0, 5, 0, 1]);
+    /// assert_eq!(v.is_nonzero(), m32x4::new([m32::FALSE, m32::TRUE, m32::FALSE, m32::TRUE]));
+    /// ```
+    #[inline]
+    pub fn is_nonzero(self) -> <Self as Masked>::Mask
+    where
+        B: PartialEq + Default,
+    {
+        self.ne(Self::default())
+    }
+
+    /// Counts the lanes equal to the given value.
+    ///
+    /// Useful for example for counting newlines or delimiters in a chunk of bytes.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let chunk = u8x8::new(*b"a,b,,c\n,");
+    /// assert_eq!(chunk.count_eq(b','), 4);
+    /// assert_eq!(chunk.count_eq(b'\n'), 1);
+    /// ```
+    #[inline]
+    pub fn count_eq(self, value: B) -> usize
+    where
+        B: PartialEq,
+    {
+        self.data.iter().filter(|&&lane| lane == value).count()
+    }
+
+    /// Counts how many lanes at the beginning of the vector are equal to the given value.
+    ///
+    /// This is the length of the run of `value` starting at lane `0`, for example the leading
+    /// whitespace of a chunk of text. It is computed from the [`cmp_ne_mask`][Vector::cmp_ne_mask]
+    /// bitmask, so the vector can have at most 32 lanes (checked at compile time).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let chunk = u8x8::new(*b"  ab  c ");
+    /// assert_eq!(chunk.leading_eq(b' '), 2);
+    /// assert_eq!(chunk.trailing_eq(b' '), 1);
+    /// assert_eq!(chunk.leading_eq(b'x'), 0);
+    /// ```
+    #[inline]
+    pub fn leading_eq(self, value: B) -> usize
+    where
+        B: PartialEq,
+    {
+        let different = self.cmp_ne_mask(Self::splat(value));
+        (different.trailing_zeros() as usize).min(S)
+    }
+
+    /// Counts how many lanes at the end of the vector are equal to the given value.
+    ///
+    /// This is the counterpart of [`leading_eq`][Vector::leading_eq], counting from the last lane
+    /// backwards.
+    #[inline]
+    pub fn trailing_eq(self, value: B) -> usize
+    where
+        B: PartialEq,
+    {
+        let different = self.cmp_ne_mask(Self::splat(value));
+        // Move the last lane to the top bit
+        let different = different << (32 - S);
+        (different.leading_zeros() as usize).min(S)
+    }
+}
+
+impl<A, B, const S: usize> Vector<A, B, S>
+where
+    A: Align,
+    B: Mask,
+{
+    /// Packs the lanes of a mask vector into bits of an integer.
+    ///
+    /// The bit `i` of the result is set if lane `i` is true (the equivalent of the `movemask`
+    /// instructions). The vector can have at most 32 lanes (checked at compile time).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = i32x4::new([-1, 2, -3, 4]);
+    /// let negative = a.lt(i32x4::default());
+    /// assert_eq!(negative.to_bitmask(), 0b0101);
+    /// ```
+    #[inline]
+    pub fn to_bitmask(self) -> u32 {
+        #[allow(clippy::let_unit_value)]
+        let () = BitmaskCheck::<S>::FITS;
+        let mut result = 0;
+        for (i, lane) in self.data.iter().enumerate() {
+            if lane.bool() {
+                result |= 1 << i;
+            }
+        }
+        result
+    }
+}
+
+impl<A, B, const S: usize> Vector<A, B, S>
+where
+    A: Align,
+    B: Pod,
+{
+    /// Views the vector as mutable raw bytes.
+    ///
+    /// The lanes are in the native endianness. This is available only for the base types that
+    /// can hold any bit pattern (not for masks and bools).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let mut v = u16x2::default();
+    /// v.as_bytes_mut().copy_from_slice(&[1u16.to_ne_bytes(), 2u16.to_ne_bytes()].concat());
+    /// assert_eq!(v, u16x2::new([1, 2]));
+    /// ```
+    #[inline]
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        let len = mem::size_of_val(&self.data);
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr().cast(), len) }
+    }
+
+    /// Loads the vector from raw bytes.
+    ///
+    /// This is the inverse of [`as_bytes`][Vector::as_bytes]. The lanes are expected to be in the
+    /// native endianness. The bytes don't need to be aligned in any way.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 2, 3, 4]);
+    /// assert_eq!(u32x4::from_bytes(v.as_bytes()), v);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the length of `bytes` doesn't match the size of the vector.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::assert_size();
+        assert_eq!(
+            bytes.len(),
+            mem::size_of::<Self>(),
+            "Creating vector from the wrong number of bytes (expected {}, got {})",
+            mem::size_of::<Self>(),
+            bytes.len(),
+        );
+        Self {
+            _align: [],
+            data: unsafe { ptr::read_unaligned(bytes.as_ptr().cast()) },
+        }
+    }
+
+    /// Loads the vector from raw bytes, with lanes in the big endian byte order.
+    ///
+    /// Like [`from_bytes`][Vector::from_bytes], but the lanes are converted from big endian (the
+    /// network byte order). To read several differently typed fields at once, see the
+    /// [`bytes`][crate::bytes] module.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u16x2::from_be_bytes(&[0x01, 0x02, 0x03, 0x04]);
+    /// assert_eq!(v, u16x2::new([0x0102, 0x0304]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the length of `bytes` doesn't match the size of the vector.
+    #[inline]
+    pub fn from_be_bytes(bytes: &[u8]) -> Self
+    where
+        B: PrimInt,
+    {
+        let mut result = Self::from_bytes(bytes);
+        for lane in &mut result.data {
+            *lane = B::from_be(*lane);
+        }
+        result
+    }
+
+    /// Loads the vector from raw bytes, with lanes in the little endian byte order.
+    ///
+    /// Like [`from_be_bytes`][Vector::from_be_bytes], but for little endian lanes.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u16x2::from_le_bytes(&[0x01, 0x02, 0x03, 0x04]);
+    /// assert_eq!(v, u16x2::new([0x0201, 0x0403]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the length of `bytes` doesn't match the size of the vector.
+    #[inline]
+    pub fn from_le_bytes(bytes: &[u8]) -> Self
+    where
+        B: PrimInt,
+    {
+        let mut result = Self::from_bytes(bytes);
+        for lane in &mut result.data {
+            *lane = B::from_le(*lane);
+        }
+        result
+    }
+}
+
+impl<A, B, const S: usize> Vector<A, B, S>
+where
+    A: Align,
+    B: Repr + PrimInt,
+{
+    /// Lane-wise shift left, with the shift amount masked to the bit width.
+    ///
+    /// The `<<` operator panics (in debug builds) if the shift amount is at least the number of
+    /// bits of the lane. This instead uses only the low bits of the amount (eg. shifting a `u32`
+    /// by `32` is the same as shifting by `0` and by `40` the same as by `8`), same as
+    /// [`u32::wrapping_shl`] and many SIMD instructions.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::splat(1);
+    /// let shifted = v.wrapping_shl(u32x4::new([0, 31, 32, 40]));
+    /// assert_eq!(shifted, u32x4::new([1, 1 << 31, 1, 1 << 8]));
+    /// ```
+    #[inline]
+    pub fn wrapping_shl(self, rhs: Self) -> Self {
+        let mask = B::from(mem::size_of::<B>() * 8 - 1).unwrap();
+        let mut result = self;
+        for (res, &r) in result.data.iter_mut().zip(rhs.data.iter()) {
+            *res = *res << (r & mask).to_usize().unwrap();
+        }
+        result
+    }
+
+    /// Lane-wise shift right, with the shift amount masked to the bit width.
+    ///
+    /// See [`wrapping_shl`][Vector::wrapping_shl] for the details. The shift is arithmetic for
+    /// the signed types and logical for the unsigned ones, same as with the `>>` operator.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::splat(1 << 31);
+    /// let shifted = v.wrapping_shr(u32x4::new([0, 31, 32, 40]));
+    /// assert_eq!(shifted, u32x4::new([1 << 31, 1, 1 << 31, 1 << 23]));
+    /// ```
+    #[inline]
+    pub fn wrapping_shr(self, rhs: Self) -> Self {
+        let mask = B::from(mem::size_of::<B>() * 8 - 1).unwrap();
+        let mut result = self;
+        for (res, &r) in result.data.iter_mut().zip(rhs.data.iter()) {
+            *res = *res >> (r & mask).to_usize().unwrap();
+        }
+        result
+    }
+
+    /// Lane-wise division, rounding towards negative infinity.
+    ///
+    /// The `/` operator rounds towards zero, which makes a difference for negative results.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = i32x2::new([-7, 7]);
+    /// assert_eq!(v.div_floor(i32x2::splat(2)), i32x2::new([-4, 3]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// On division by zero or an overflow, same as the `/` operator.
+    #[inline]
+    pub fn div_floor(self, rhs: Self) -> Self {
+        let zero = B::zero();
+        let mut result = self;
+        for (res, &r) in result.data.iter_mut().zip(rhs.data.iter()) {
+            let q = *res / r;
+            let rem = *res % r;
+            *res = if rem != zero && ((rem < zero) != (r < zero)) {
+                q - B::one()
+            } else {
+                q
+            };
+        }
+        result
+    }
+
+    /// Lane-wise division, rounding towards positive infinity.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = i32x2::new([-7, 7]);
+    /// assert_eq!(v.div_ceil(i32x2::splat(2)), i32x2::new([-3, 4]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// On division by zero or an overflow, same as the `/` operator.
+    #[inline]
+    pub fn div_ceil(self, rhs: Self) -> Self {
+        let zero = B::zero();
+        let mut result = self;
+        for (res, &r) in result.data.iter_mut().zip(rhs.data.iter()) {
+            let q = *res / r;
+            let rem = *res % r;
+            *res = if rem != zero && ((rem > zero) == (r > zero)) {
+                q + B::one()
+            } else {
+                q
+            };
+        }
+        result
+    }
+
+    /// Lane-wise division, returning `None` instead of panicking.
+    ///
+    /// Integer division by zero (or the overflowing division of the minimum value by `-1`)
+    /// panics, just like with the scalars. As the operation is done on the whole vector, a single
+    /// such lane invalidates the whole result and `None` is returned, even if the other lanes
+    /// would be fine.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = u32x4::new([10, 20, 30, 40]);
+    /// assert_eq!(a.checked_div(u32x4::new([2, 5, 3, 4])), Some(u32x4::new([5, 4, 10, 10])));
+    /// assert_eq!(a.checked_div(u32x4::new([2, 0, 3, 4])), None);
+    /// ```
+    #[inline]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        let mut result = self;
+        for (res, r) in result.data.iter_mut().zip(rhs.data.iter()) {
+            *res = res.checked_div(r)?;
+        }
+        Some(result)
+    }
+
+    /// Lane-wise remainder, returning `None` instead of panicking.
+    ///
+    /// Like [`checked_div`][Vector::checked_div], a single lane with zero divisor (or overflow)
+    /// makes the whole result `None`.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = i32x2::new([7, -7]);
+    /// assert_eq!(a.checked_rem(i32x2::new([3, 3])), Some(i32x2::new([1, -1])));
+    /// assert_eq!(a.checked_rem(i32x2::new([3, 0])), None);
+    /// ```
+    #[inline]
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        let mut result = self;
+        for (res, &r) in result.data.iter_mut().zip(rhs.data.iter()) {
+            // Filters out the same problematic cases as the remainder has
+            res.checked_div(&r)?;
+            *res = *res % r;
+        }
+        Some(result)
+    }
+
+    /// Sums the lanes together, saturating at the bounds of the lane type.
+    ///
+    /// Unlike [`horizontal_sum`][Vector::horizontal_sum], which overflows the same way as the
+    /// scalar addition does, this clamps the result to the maximum (or minimum) of the type. The
+    /// lanes are added in order, starting from the first one. For signed types, this means that
+    /// once the running sum saturates, later lanes of the opposite sign bring it back from the
+    /// bound.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u8x4::new([100, 100, 100, 1]);
+    /// assert_eq!(v.saturating_horizontal_sum(), 255);
+    /// ```
+    #[inline]
+    pub fn saturating_horizontal_sum(self) -> B {
+        self.data[1..]
+            .iter()
+            .fold(self.data[0], |acc, &lane| acc.saturating_add(lane))
+    }
+
+    /// Funnel shift left across two vectors.
+    ///
+    /// Each lane of `self` and the corresponding lane of `low` are concatenated into a value of
+    /// double width (`self` being the upper half), which is shifted left by `n` bits and the upper
+    /// half is returned. That is, `(self << n) | (low >> (bits - n))`, with the shifts being
+    /// logical even for signed types (this is what the `shld` instruction does). It allows
+    /// extracting bits that straddle two words of a bit stream.
+    ///
+    /// The shift amount is taken modulo the number of bits of the lane, so shifting by `0` (or by
+    /// the whole width) returns `self` unchanged.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let hi = u32x2::new([0x1122_3344, 0xFFFF_FFFF]);
+    /// let lo = u32x2::new([0x5566_7788, 0]);
+    /// assert_eq!(hi.funnel_shl(lo, 8), u32x2::new([0x2233_4455, 0xFFFF_FF00]));
+    /// ```
+    #[inline]
+    pub fn funnel_shl(self, low: Self, n: u32) -> Self {
+        let bits = mem::size_of::<B>() as u32 * 8;
+        let n = n % bits;
+        if n == 0 {
+            return self;
+        }
+        let mut result = self;
+        for (res, l) in result.data.iter_mut().zip(low.data.iter()) {
+            *res = res.unsigned_shl(n) | l.unsigned_shr(bits - n);
+        }
+        result
+    }
+
+    /// Sign-extends the low `BITS` bits of each lane to the whole lane.
+    ///
+    /// The low `BITS` bits are interpreted as a signed (two's complement) number, the higher bits
+    /// are replaced by copies of its sign bit. This is useful for unpacking bit-packed signed
+    /// values, like 12-bit samples stored in 16-bit lanes. The `BITS` must be between 1 and the
+    /// number of bits of the lane (checked at compile time).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let samples = i16x4::new([0x07FF, 0x0800, 0x7FFF, 0x0001]);
+    /// assert_eq!(samples.sign_extend_from::<12>(), i16x4::new([2047, -2048, -1, 1]));
+    /// ```
+    #[inline]
+    pub fn sign_extend_from<const BITS: u32>(self) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = ExtendCheck::<B, BITS>::FITS;
+        let shift = mem::size_of::<B>() as u32 * 8 - BITS;
+        let mut result = self;
+        for res in &mut result.data {
+            *res = res.unsigned_shl(shift).signed_shr(shift);
+        }
+        result
+    }
+
+    /// Zero-extends the low `BITS` bits of each lane to the whole lane.
+    ///
+    /// The low `BITS` bits are kept and the higher bits are cleared. This is the unsigned
+    /// counterpart of [`sign_extend_from`][Vector::sign_extend_from]. The `BITS` must be between
+    /// 1 and the number of bits of the lane (checked at compile time).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let samples = i16x4::new([0x07FF, 0x0800, 0x7FFF, -1]);
+    /// assert_eq!(samples.zero_extend_from::<12>(), i16x4::new([0x7FF, 0x800, 0xFFF, 0xFFF]));
+    /// ```
+    #[inline]
+    pub fn zero_extend_from<const BITS: u32>(self) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = ExtendCheck::<B, BITS>::FITS;
+        let shift = mem::size_of::<B>() as u32 * 8 - BITS;
+        let mut result = self;
+        for res in &mut result.data {
+            *res = res.unsigned_shl(shift).unsigned_shr(shift);
+        }
+        result
+    }
+
+    /// Reverses the order of bytes in each lane.
+    ///
+    /// This converts each lane between little and big endian. The lanes themselves stay in
+    /// place.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x2::new([0x1234_5678, 0xAABB_CCDD]);
+    /// assert_eq!(v.reverse_bytes_in_lane(), u32x2::new([0x7856_3412, 0xDDCC_BBAA]));
+    /// ```
+    #[inline]
+    pub fn reverse_bytes_in_lane(self) -> Self {
+        let mut result = self;
+        for res in &mut result.data {
+            *res = res.swap_bytes();
+        }
+        result
+    }
+
+    /// Rotates the bytes in each lane by `n` positions.
+    ///
+    /// The bytes move towards the more significant end, the most significant ones wrapping
+    /// around to the least significant positions. This is the same as `rotate_left` by `8 * n`
+    /// bits, independent of the endianness. Rotating by the number of bytes of the lane (or its
+    /// multiple) leaves the lane unchanged.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x2::new([0x1122_3344, 0xAABB_CCDD]);
+    /// assert_eq!(v.rotate_bytes_in_lane(1), u32x2::new([0x2233_4411, 0xBBCC_DDAA]));
+    /// ```
+    #[inline]
+    pub fn rotate_bytes_in_lane(self, n: u32) -> Self {
+        let bits = (n % mem::size_of::<B>() as u32) * 8;
+        let mut result = self;
+        for res in &mut result.data {
+            *res = res.rotate_left(bits);
+        }
+        result
+    }
+}
+
+impl<A, B, const S: usize> Vector<A, B, S>
+where
+    A: Align,
+    B: Repr + PrimInt + Unsigned,
+{
+    /// Rounds each lane up to the nearest power of two.
+    ///
+    /// Same as the scalar method of the primitive types, zero becomes one. If the result doesn't
+    /// fit, this panics in debug builds and returns zero in release builds.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 3, 5, 8]);
+    /// assert_eq!(v.next_power_of_two(), u32x4::new([1, 4, 8, 8]));
+    /// ```
+    #[inline]
+    pub fn next_power_of_two(self) -> Self {
+        let bits = mem::size_of::<B>() as u32 * 8;
+        let mut result = self;
+        for res in &mut result.data {
+            *res = if *res <= B::one() {
+                B::one()
+            } else {
+                let shift = bits - (*res - B::one()).leading_zeros();
+                if shift < bits {
+                    B::one() << shift as usize
+                } else if cfg!(debug_assertions) {
+                    panic!("attempt to compute next power of two with overflow");
+                } else {
+                    B::zero()
+                }
+            };
+        }
+        result
+    }
+
+    /// Lane-wise base 2 logarithm, rounded down.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u32x4::new([1, 3, 5, 8]);
+    /// assert_eq!(v.ilog2(), u32x4::new([0, 1, 2, 3]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If any of the lanes is zero, same as the scalar method of the primitive types.
+    #[inline]
+    pub fn ilog2(self) -> Self {
+        let bits = mem::size_of::<B>() as u32 * 8;
+        let mut result = self;
+        for res in &mut result.data {
+            assert!(
+                *res != B::zero(),
+                "argument of integer logarithm must be positive"
+            );
+            *res = B::from(bits - 1 - res.leading_zeros()).unwrap();
+        }
+        result
+    }
+
+    /// Lane-wise average, rounding up.
+    ///
+    /// Computes `(self + other + 1) / 2` without overflowing (like the `pavgb` and `pavgw`
+    /// instructions).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = u8x4::new([0, 1, 255, 10]);
+    /// let b = u8x4::new([0, 2, 255, 20]);
+    /// assert_eq!(a.avg(b), u8x4::new([0, 2, 255, 15]));
+    /// ```
+    #[inline]
+    pub fn avg(self, other: Self) -> Self {
+        let mut result = self;
+        for (res, &o) in result.data.iter_mut().zip(other.data.iter()) {
+            *res = (*res | o) - ((*res ^ o) >> 1);
+        }
+        result
+    }
+
+    /// Halves the number of lanes by averaging adjacent pairs.
+    ///
+    /// The lane `i` of the result is the [`avg`][Vector::avg] (rounding up) of the lanes `2 * i`
+    /// and `2 * i + 1`. This is the horizontal half of a 2x2 box downscaling of an image.
+    ///
+    /// The result must have exactly half the lanes (checked at compile time).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u8x8::new([0, 2, 4, 7, 10, 10, 0, 255]);
+    /// let half: u8x4 = v.downsample_2x();
+    /// assert_eq!(half, u8x4::new([1, 6, 10, 128]));
+    /// ```
+    #[inline]
+    pub fn downsample_2x<A2: Align, const N: usize>(self) -> Vector<A2, B, N> {
+        #[allow(clippy::let_unit_value)]
+        let () = SizeCheck::<S, N, 2>::CHUNKS;
+        Vector::<A2, B, N>::assert_size();
+        let mut data = MaybeUninit::<Vector<A2, B, N>>::uninit();
+        unsafe {
+            for i in 0..N {
+                let (a, b) = (self[2 * i], self[2 * i + 1]);
+                ptr::write(
+                    data.as_mut_ptr().cast::<B>().add(i),
+                    (a | b) - ((a ^ b) >> 1),
+                );
+            }
+            data.assume_init()
+        }
+    }
+
+    /// Converts each lane to its Gray code.
+    ///
+    /// In the Gray code, consecutive numbers differ in exactly one bit. This computes
+    /// `x ^ (x >> 1)` per lane.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u8x4::new([0, 1, 2, 3]);
+    /// assert_eq!(v.to_gray(), u8x4::new([0b00, 0b01, 0b11, 0b10]));
+    /// ```
+    #[inline]
+    pub fn to_gray(self) -> Self {
+        let mut result = self;
+        for res in &mut result.data {
+            *res = *res ^ (*res >> 1);
+        }
+        result
+    }
+
+    /// Converts each lane from the Gray code back to the ordinary binary number.
+    ///
+    /// This is the inverse of [`to_gray`][Vector::to_gray].
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u8x4::new([0b00, 0b01, 0b11, 0b10]);
+    /// assert_eq!(v.from_gray(), u8x4::new([0, 1, 2, 3]));
+    /// ```
+    #[inline]
+    pub fn from_gray(self) -> Self {
+        let bits = mem::size_of::<B>() * 8;
+        let mut result = self;
+        for res in &mut result.data {
+            let mut shift = 1;
+            while shift < bits {
+                *res = *res ^ (*res >> shift);
+                shift *= 2;
+            }
+        }
+        result
+    }
+}
+
+impl<A, B, const S: usize> Vector<A, B, S>
+where
+    A: Align,
+    B: WideMul,
+{
+    /// Lane-wise multiplication, returning the high half of the full product.
+    ///
+    /// The lanes are multiplied into a twice as wide result (so it can't overflow) and the upper
+    /// half of the bits is kept. This is useful for fixed-point arithmetic. Both signed and
+    /// unsigned types are supported, the signed multiplication is used for the signed ones.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u16x2::splat(0xFFFF);
+    /// assert_eq!(v.mul_hi(v), u16x2::splat(0xFFFE));
+    /// let v = i16x2::new([-2, 0x4000]);
+    /// assert_eq!(v.mul_hi(i16x2::splat(0x4000)), i16x2::new([-1, 0x1000]));
+    /// ```
+    #[inline]
+    pub fn mul_hi(self, other: Self) -> Self {
+        let mut result = self;
+        for (res, &o) in result.data.iter_mut().zip(other.data.iter()) {
+            *res = res.mul_hi(o);
+        }
+        result
+    }
+
+    /// Lane-wise multiplication into a vector of twice as wide lanes.
+    ///
+    /// The result has the same number of lanes, each holding the full product (which can't
+    /// overflow). The type of the result needs to be specified, usually by a turbofish.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = u16x2::splat(0xFFFF);
+    /// assert_eq!(v.mul_widen::<u32x2>(v), u32x2::splat(0xFFFE0001));
+    /// ```
+    #[inline]
+    pub fn mul_widen<W>(self, other: Self) -> W
+    where
+        W: From<[B::Wide; S]>,
+    {
+        let mut data = MaybeUninit::<[B::Wide; S]>::uninit();
+        unsafe {
+            for i in 0..S {
+                ptr::write(
+                    data.as_mut_ptr().cast::<B::Wide>().add(i),
+                    self.data[i].mul_wide(other.data[i]),
+                );
+            }
+            W::from(data.assume_init())
+        }
+    }
+}
+
+impl<A, B, const S: usize> Vector<A, B, S>
+where
+    A: Align,
+    B: SignedInt,
+{
+    /// Lane-wise wrapping negation.
+    ///
+    /// Unlike the `-` operator, this doesn't panic on the minimal value (which has no positive
+    /// counterpart), it stays the same instead.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = i32x2::new([i32::MIN, 5]);
+    /// assert_eq!(v.wrapping_neg(), i32x2::new([i32::MIN, -5]));
+    /// ```
+    #[inline]
+    pub fn wrapping_neg(self) -> Self {
+        let mut result = self;
+        for res in &mut result.data {
+            *res = res.wrapping_neg();
+        }
+        result
+    }
+
+    /// Lane-wise wrapping absolute value.
+    ///
+    /// The absolute value of the minimal value doesn't fit, so it wraps back to itself.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = i8x4::new([i8::MIN, -5, 0, 7]);
+    /// assert_eq!(v.wrapping_abs(), i8x4::new([i8::MIN, 5, 0, 7]));
+    /// ```
+    #[inline]
+    pub fn wrapping_abs(self) -> Self {
+        let mut result = self;
+        for res in &mut result.data {
+            *res = res.wrapping_abs();
+        }
+        result
+    }
+}
+
+impl<A, B, const S: usize> Vector<A, B, S>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    /// Fused multiply-add. Computes (self * a) + b with only one rounding
+    /// error, yielding a more accurate result than an unfused multiply-add.
+    ///
+    /// Using mul_add can be more performant than an unfused multiply-add if the
+    /// target architecture has a dedicated fma CPU instruction.
+    #[inline]
+    pub fn mul_add(self, a: Self, b: Self) -> Self {
+        let mut result = Self::splat(B::zero());
+        for ((res, &s), (&a, &b)) in result
+            .data
+            .iter_mut()
+            .zip(self.data.iter())
+            .zip(a.data.iter().zip(b.data.iter()))
+        {
+            *res = s.mul_add(a, b);
+        }
+        result
+    }
+
+    /// Fused multiply-subtract. Computes (self * a) - b with only one rounding error.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x2::new([2.0, -2.0]);
+    /// let r = v.mul_sub(f32x2::splat(3.0), f32x2::splat(1.0));
+    /// assert_eq!(r, f32x2::new([5.0, -7.0]));
+    /// ```
+    #[inline]
+    pub fn mul_sub(self, a: Self, b: Self) -> Self {
+        self.mul_add(a, -b)
+    }
+
+    /// Negated fused multiply-add. Computes -(self * a) + b with only one rounding error.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x2::new([2.0, -2.0]);
+    /// let r = v.neg_mul_add(f32x2::splat(3.0), f32x2::splat(1.0));
+    /// assert_eq!(r, f32x2::new([-5.0, 7.0]));
+    /// ```
+    #[inline]
+    pub fn neg_mul_add(self, a: Self, b: Self) -> Self {
+        (-self).mul_add(a, b)
+    }
+
+    /// Negated fused multiply-subtract. Computes -(self * a) - b with only one rounding error.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x2::new([2.0, -2.0]);
+    /// let r = v.neg_mul_sub(f32x2::splat(3.0), f32x2::splat(1.0));
+    /// assert_eq!(r, f32x2::new([-7.0, 5.0]));
+    /// ```
+    #[inline]
+    pub fn neg_mul_sub(self, a: Self, b: Self) -> Self {
+        (-self).mul_add(a, -b)
+    }
+
+    /// Lane-wise length of the hypotenuse, `sqrt(self² + other²)`.
+    ///
+    /// Unlike the naive formula, this doesn't overflow or underflow in the intermediate results.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let x = f32x2::new([3.0, 3e30]);
+    /// let y = f32x2::new([4.0, 4e30]);
+    /// assert_eq!(x.hypot(y), f32x2::new([5.0, 5e30]));
+    /// ```
+    #[inline]
+    pub fn hypot(self, other: Self) -> Self {
+        let mut result = self;
+        for (res, &o) in result.data.iter_mut().zip(other.data.iter()) {
+            *res = res.hypot(o);
+        }
+        result
+    }
+
+    /// Lane-wise fractional part.
+    ///
+    /// This is `x - x.trunc()`, therefore the result has the same sign as the original value.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x2::new([1.75, -1.25]);
+    /// assert_eq!(v.fract(), f32x2::new([0.75, -0.25]));
+    /// ```
+    #[inline]
+    pub fn fract(self) -> Self {
+        let mut result = self;
+        for res in &mut result.data {
+            *res = res.fract();
+        }
+        result
+    }
+
+    /// Splits the lanes into the integer and fractional parts.
+    ///
+    /// Returns the same results as [`trunc`][Float::trunc] and [`fract`][Vector::fract], but in
+    /// one pass. Both parts have the same sign as the original value.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let (int, fract) = f32x2::new([1.75, -1.25]).modf();
+    /// assert_eq!(int, f32x2::new([1.0, -1.0]));
+    /// assert_eq!(fract, f32x2::new([0.75, -0.25]));
+    /// ```
+    #[inline]
+    pub fn modf(self) -> (Self, Self) {
+        let mut int = self;
+        let mut fract = self;
+        for (i, f) in int.data.iter_mut().zip(fract.data.iter_mut()) {
+            *i = i.trunc();
+            *f = *f - *i;
+        }
+        (int, fract)
+    }
+
+    /// Converts the lanes from radians to degrees.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use std::f32::consts::PI;
+    ///
+    /// let deg = f32x2::splat(PI).to_degrees();
+    /// assert!(deg.iter().all(|d| (d - 180.0).abs() < 1e-4));
+    /// ```
+    #[inline]
+    pub fn to_degrees(self) -> Self {
+        let mut result = self;
+        for res in &mut result.data {
+            *res = res.to_degrees();
+        }
+        result
+    }
+
+    /// Converts the lanes from degrees to radians.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use std::f32::consts::PI;
+    ///
+    /// let rad = f32x2::new([180.0, -90.0]).to_radians();
+    /// assert!((rad[0] - PI).abs() < 1e-6);
+    /// assert!((rad[1] + PI / 2.0).abs() < 1e-6);
+    /// ```
+    #[inline]
+    pub fn to_radians(self) -> Self {
+        let mut result = self;
+        for res in &mut result.data {
+            *res = res.to_radians();
+        }
+        result
+    }
+
+    /// Lane-wise four-quadrant arctangent of `self` (`y`) and `other` (`x`).
+    ///
+    /// The result is in radians, in the `[-π, π]` range, with the quadrant given by the signs of
+    /// both inputs (unlike a plain `atan(y / x)`).
+    ///
+    /// Note that this calls the scalar function from the math library for each lane. The compiler
+    /// usually isn't able to vectorize that, so this is for convenience rather than speed.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// use std::f32::consts::PI;
+    ///
+    /// let y = f32x2::new([1.0, -1.0]);
+    /// let x = f32x2::new([-1.0, -1.0]);
+    /// let angle = y.atan2(x);
+    /// assert!((angle[0] - 3.0 * PI / 4.0).abs() < 1e-6);
+    /// assert!((angle[1] + 3.0 * PI / 4.0).abs() < 1e-6);
+    /// ```
+    #[inline]
+    pub fn atan2(self, other: Self) -> Self {
+        let mut result = self;
+        for (res, &o) in result.data.iter_mut().zip(other.data.iter()) {
+            *res = res.atan2(o);
+        }
+        result
+    }
+
+    /// The dot product of two vectors.
+    ///
+    /// The products are accumulated with the fused multiply-add.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = f32x4::new([1.0, 2.0, 3.0, 4.0]);
+    /// let b = f32x4::new([2.0, 0.0, -1.0, 0.5]);
+    /// assert_eq!(a.dot(b), 1.0);
+    /// ```
+    #[inline]
+    pub fn dot(self, other: Self) -> B {
+        self.iter()
+            .zip(other.iter())
+            .fold(B::zero(), |acc, (&a, &b)| a.mul_add(b, acc))
+    }
+
+    /// Cosine of the angle between two vectors.
+    ///
+    /// This is the dot product divided by the lengths of both vectors. It is `1` for vectors
+    /// pointing in the same direction, `0` for orthogonal ones and `-1` for opposite ones.
+    ///
+    /// The angle is not defined if any of the vectors is zero. In such case, this returns `0`
+    /// (instead of `NaN` the naive formula would produce), treating the zero vector as orthogonal
+    /// to everything.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = f32x2::new([1.0, 1.0]);
+    /// assert!((a.cosine_similarity(f32x2::new([3.0, 3.0])) - 1.0).abs() < 1e-6);
+    /// assert_eq!(a.cosine_similarity(f32x2::new([1.0, -1.0])), 0.0);
+    /// assert_eq!(a.cosine_similarity(f32x2::splat(0.0)), 0.0);
+    /// ```
+    #[inline]
+    pub fn cosine_similarity(self, other: Self) -> B {
+        let norms = self.magnitude() * other.magnitude();
+        if norms == B::zero() {
+            B::zero()
+        } else {
+            self.dot(other) / norms
+        }
+    }
+
+    /// Sums the squares of the lanes.
+    ///
+    /// This is the same as `self.dot(self)` (the squared [`magnitude`][Vector::magnitude]), but
+    /// states the intent more clearly. The squares are accumulated with the fused multiply-add.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// assert_eq!(f32x4::new([1.0, 2.0, 3.0, 4.0]).sum_of_squares(), 30.0);
+    /// ```
+    #[inline]
+    pub fn sum_of_squares(self) -> B {
+        self.iter().fold(B::zero(), |acc, &x| x.mul_add(x, acc))
+    }
+
+    /// Squared euclidean distance between two vectors.
+    ///
+    /// This treats the vectors as points in `S`-dimensional space. It is cheaper than
+    /// [`distance`][Vector::distance] and sufficient if the distances are only compared.
+    ///
+    /// The squares are accumulated with the fused multiply-add.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = f32x4::new([0.0, 0.0, 0.0, 0.0]);
+    /// let b = f32x4::new([1.0, 2.0, 2.0, 0.0]);
+    /// assert_eq!(a.distance_sq(b), 9.0);
+    /// ```
+    #[inline]
+    pub fn distance_sq(self, other: Self) -> B {
+        (self - other).sum_of_squares()
+    }
+
+    /// Euclidean distance between two vectors.
+    ///
+    /// This is the square root of [`distance_sq`][Vector::distance_sq].
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = f32x2::new([1.0, 1.0]);
+    /// let b = f32x2::new([4.0, 5.0]);
+    /// assert_eq!(a.distance(b), 5.0);
+    /// ```
+    #[inline]
+    pub fn distance(self, other: Self) -> B {
+        self.distance_sq(other).sqrt()
+    }
+
+    /// Checks if any of the lanes is `NaN`.
+    ///
+    /// This is a single-call guard, equivalent to checking each lane with `is_nan`. It doesn't
+    /// short-circuit, which allows the compiler to turn it into a vector comparison and a single
+    /// reduction.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// assert!(!f32x4::new([1.0, f32::INFINITY, -0.0, 4.0]).any_nan());
+    /// assert!(f32x4::new([1.0, 2.0, f32::NAN, 4.0]).any_nan());
+    /// ```
+    #[inline]
+    pub fn any_nan(self) -> bool {
+        self.data
+            .iter()
+            .fold(false, |acc, lane| acc | lane.is_nan())
+    }
+
+    /// Checks if all the lanes are finite (neither infinite nor `NaN`).
+    ///
+    /// Like [`any_nan`][Vector::any_nan], it processes all the lanes without short-circuiting.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// assert!(f32x4::new([1.0, f32::MAX, -0.0, 4.0]).all_finite());
+    /// assert!(!f32x4::new([1.0, f32::NEG_INFINITY, 3.0, 4.0]).all_finite());
+    /// ```
+    #[inline]
+    pub fn all_finite(self) -> bool {
+        self.data
+            .iter()
+            .fold(true, |acc, lane| acc & lane.is_finite())
+    }
+
+    /// Lane-wise minimum, using the total ordering of floats.
+    ///
+    /// Unlike [`minimum`][Vector::minimum], this follows the `total_cmp` ordering of the standard
+    /// library: `-NaN < -∞ < … < -0.0 < +0.0 < … < +∞ < +NaN`. Therefore, the result is fully
+    /// deterministic even in presence of `NaN`s and signed zeroes.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = f32x2::new([-0.0, f32::NAN]);
+    /// let b = f32x2::new([0.0, 1.0]);
+    /// let min = a.min_total(b);
+    /// assert!(min[0].is_sign_negative());
+    /// assert_eq!(min[1], 1.0);
+    /// ```
+    #[inline]
+    pub fn min_total(self, other: Self) -> Self
+    where
+        B: TotalOrder,
+    {
+        let mut result = self;
+        for (res, o) in result.data.iter_mut().zip(other.data.iter()) {
+            if o.total_cmp(res) == Ordering::Less {
+                *res = *o;
+            }
+        }
+        result
+    }
+
+    /// Lane-wise maximum, using the total ordering of floats.
+    ///
+    /// See [`min_total`][Vector::min_total] for the ordering used.
+    #[inline]
+    pub fn max_total(self, other: Self) -> Self
+    where
+        B: TotalOrder,
+    {
+        let mut result = self;
+        for (res, o) in result.data.iter_mut().zip(other.data.iter()) {
+            if o.total_cmp(res) == Ordering::Greater {
+                *res = *o;
+            }
+        }
+        result
+    }
+
+    /// Sorts the lanes in ascending order, using the total ordering of floats.
+    ///
+    /// This is like [`sort_lanes`][Vector::sort_lanes], but the result is deterministic even with
+    /// `NaN`s in the vector, using the same ordering as [`min_total`][Vector::min_total].
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x4::new([1.0, f32::NAN, 0.0, -0.0]).sort_lanes_total();
+    /// assert_eq!(v[0].to_bits(), (-0.0f32).to_bits());
+    /// assert_eq!(v[1].to_bits(), 0.0f32.to_bits());
+    /// assert_eq!(v[2], 1.0);
+    /// assert!(v[3].is_nan());
+    /// ```
+    #[inline]
+    pub fn sort_lanes_total(self) -> Self
+    where
+        B: TotalOrder,
+    {
+        self.sort_lanes_by(B::total_cmp)
+    }
+
+    /// Converts the lanes to an integer type, saturating at its bounds.
+    ///
+    /// The fractional part is truncated (rounded towards zero). Values out of the range of the
+    /// target type (including the infinities) are clamped to its minimum or maximum and `NaN`
+    /// becomes `0`. This is the same as the `as` operator does on scalars, but explicitly named.
+    ///
+    /// The target type is usually inferred.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x4::new([1.9, -1e20, f32::NAN, 300.0]);
+    /// let r: i8x4 = v.saturating_cast();
+    /// assert_eq!(r, i8x4::new([1, -128, 0, 127]));
+    /// ```
+    #[inline]
+    pub fn saturating_cast<A2, T>(self) -> Vector<A2, T, S>
+    where
+        A2: Align,
+        T: Repr + PrimInt,
+    {
+        Vector::<A2, T, S>::assert_size();
+        let mut data = MaybeUninit::<Vector<A2, T, S>>::uninit();
+        unsafe {
+            for i in 0..S {
+                let lane = self.data[i];
+                let converted = T::from(lane).unwrap_or_else(|| {
+                    if lane.is_nan() {
+                        T::zero()
+                    } else if lane > B::zero() {
+                        T::max_value()
+                    } else {
+                        T::min_value()
+                    }
+                });
+                ptr::write(data.as_mut_ptr().cast::<T>().add(i), converted);
+            }
+            data.assume_init()
+        }
+    }
+
+    /// Lane-wise approximate equality.
+    ///
+    /// A lane is considered equal if `|self - other| <= epsilon` (therefore, lanes with `NaN`s are
+    /// never equal). This is an absolute tolerance; for values of very different magnitudes, it
+    /// needs to be scaled accordingly.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = f32x2::new([1.0, 1.0]);
+    /// let b = f32x2::new([1.0 + 1e-7, 1.1]);
+    /// assert_eq!(a.approx_eq(b, 1e-6), m32x2::new([m32::TRUE, m32::FALSE]));
+    /// ```
+    #[inline]
+    pub fn approx_eq(self, other: Self, epsilon: B) -> <Self as Masked>::Mask {
+        let mut data = MaybeUninit::<<Self as Masked>::Mask>::uninit();
+        unsafe {
+            for i in 0..S {
+                ptr::write(
+                    data.as_mut_ptr().cast::<B::Mask>().add(i),
+                    B::Mask::from_bool((self.data[i] - other.data[i]).abs() <= epsilon),
+                );
+            }
+            data.assume_init()
+        }
+    }
+
+    /// The euclidean length of the vector.
+    ///
+    /// This is the [`distance`][Vector::distance] from the zero vector.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// assert_eq!(f32x4::new([3.0, 4.0, 0.0, 0.0]).magnitude(), 5.0);
+    /// ```
+    #[inline]
+    pub fn magnitude(self) -> B {
+        self.dot(self).sqrt()
+    }
+
+    /// Scales the vector to have the length of `1`.
+    ///
+    /// The direction is not defined for the zero vector. In such case, the zero vector is
+    /// returned (instead of the `NaN`s the division would produce).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f32x4::new([3.0, 4.0, 0.0, 0.0]);
+    /// assert_eq!(v.normalize(), f32x4::new([0.6, 0.8, 0.0, 0.0]));
+    /// assert_eq!(f32x4::default().normalize(), f32x4::default());
+    /// ```
+    #[inline]
+    pub fn normalize(self) -> Self {
+        let magnitude = self.magnitude();
+        if magnitude == B::zero() {
+            self
+        } else {
+            self / Self::splat(magnitude)
+        }
+    }
+
+    /// Sums the lanes together, with compensation of the rounding errors.
+    ///
+    /// This uses the Kahan-Babuška (Neumaier) summation, which keeps track of the low-order bits
+    /// lost in each addition and adds them back at the end. It is considerably more accurate than
+    /// [`horizontal_sum`][Vector::horizontal_sum], especially when large values cancel each other
+    /// out, but it is also slower because the additions have to be done in sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let v = f64x4::new([1.0, 1e100, 1.0, -1e100]);
+    /// assert_eq!(v.horizontal_sum(), 0.0);
+    /// assert_eq!(v.horizontal_sum_kahan(), 2.0);
+    /// ```
+    #[inline]
+    pub fn horizontal_sum_kahan(self) -> B {
+        let mut sum = B::zero();
+        let mut compensation = B::zero();
+        for &lane in &self.data {
+            let t = sum + lane;
+            if sum.abs() >= lane.abs() {
+                compensation = compensation + ((sum - t) + lane);
+            } else {
+                compensation = compensation + ((lane - t) + sum);
+            }
+            sum = t;
+        }
+        sum + compensation
+    }
+}
+
+impl<A, B> Vector<A, B, 4>
+where
+    A: Align,
+    B: Repr + Float,
+{
+    /// Cross product of 3-dimensional vectors.
+    ///
+    /// The first three lanes are treated as the `x`, `y` and `z` coordinates, the last lane is
+    /// ignored (and set to zero in the result). This is the usual layout of 3D vectors padded to a
+    /// power of two.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let x = f32x4::new([1.0, 0.0, 0.0, 0.0]);
+    /// let y = f32x4::new([0.0, 1.0, 0.0, 0.0]);
+    /// assert_eq!(x.cross3(y), f32x4::new([0.0, 0.0, 1.0, 0.0]));
+    /// ```
+    #[inline]
+    pub fn cross3(self, other: Self) -> Self {
+        const YZX: [usize; 4] = [1, 2, 0, 3];
+        const ZXY: [usize; 4] = [2, 0, 1, 3];
+        let a_yzx = Self::gather_load(self, YZX);
+        let a_zxy = Self::gather_load(self, ZXY);
+        let b_yzx = Self::gather_load(other, YZX);
+        let b_zxy = Self::gather_load(other, ZXY);
+        let mut result = a_yzx.mul_sub(b_zxy, a_zxy * b_yzx);
+        result[3] = B::zero();
+        result
+    }
+
+    /// Transforms the vector by a matrix of 3 rows.
+    ///
+    /// The lane `i` of the result is the [dot product][Vector::dot] of the row `i` with `self`.
+    /// The last lane is copied from `self`.
+    ///
+    /// With the last lane of the rows being zero, this is a multiplication by a 3×3 matrix. For
+    /// points with the last lane set to one, the last column of the rows acts as a translation
+    /// (making it an affine transformation).
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// // Swap x and y, move z by 10
+    /// let rows = [
+    ///     f32x4::new([0.0, 1.0, 0.0, 0.0]),
+    ///     f32x4::new([1.0, 0.0, 0.0, 0.0]),
+    ///     f32x4::new([0.0, 0.0, 1.0, 10.0]),
+    /// ];
+    /// let point = f32x4::new([1.0, 2.0, 3.0, 1.0]);
+    /// assert_eq!(point.transform3(rows), f32x4::new([2.0, 1.0, 13.0, 1.0]));
+    /// ```
+    #[inline]
+    pub fn transform3(self, rows: [Self; 3]) -> Self {
+        let mut result = self;
+        for (res, row) in result.iter_mut().zip(rows.iter()) {
+            *res = row.dot(self);
+        }
+        result
+    }
+
+    /// Transforms the vector by a 4×4 matrix.
+    ///
+    /// The lane `i` of the result is the [dot product][Vector::dot] of the row `i` with `self`.
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// // Translation by [1, 2, 3]
+    /// let rows = [
+    ///     f32x4::new([1.0, 0.0, 0.0, 1.0]),
+    ///     f32x4::new([0.0, 1.0, 0.0, 2.0]),
+    ///     f32x4::new([0.0, 0.0, 1.0, 3.0]),
+    ///     f32x4::new([0.0, 0.0, 0.0, 1.0]),
+    /// ];
+    /// let point = f32x4::new([5.0, 5.0, 5.0, 1.0]);
+    /// assert_eq!(point.transform4(rows), f32x4::new([6.0, 7.0, 8.0, 1.0]));
+    /// ```
+    #[inline]
+    pub fn transform4(self, rows: [Self; 4]) -> Self {
+        let mut result = self;
+        for (res, row) in result.iter_mut().zip(rows.iter()) {
+            *res = row.dot(self);
+        }
+        result
+    }
+}
+
+impl<A: Align, const S: usize> Vector<A, i16, S> {
+    /// Multiplies the lanes and adds the adjacent pairs of products together.
+    ///
+    /// The result has half the lanes of the input, each being `self[2 * i] * other[2 * i] +
+    /// self[2 * i + 1] * other[2 * i + 1]`, computed in 32 bits. This is a building block for dot
+    /// products of quantized data and corresponds to the `pmaddwd` instruction. The only case
+    /// that doesn't fit is when all four inputs are `i16::MIN`, which wraps around (same as the
+    /// instruction does).
+    ///
+    /// The result type is usually inferred, but the number of lanes must be exactly half of the
+    /// input (checked at compile time).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = i16x4::new([1, 2, 3, -4]);
+    /// let b = i16x4::new([5, 6, 7, 8]);
+    /// let r: i32x2 = a.madd(b);
+    /// assert_eq!(r, i32x2::new([17, -11]));
+    /// ```
+    #[inline]
+    pub fn madd<A2: Align, const N: usize>(self, other: Self) -> Vector<A2, i32, N> {
+        #[allow(clippy::let_unit_value)]
+        let () = SizeCheck::<S, N, 2>::CHUNKS;
+        Vector::<A2, i32, N>::assert_size();
+        let mut data = MaybeUninit::<Vector<A2, i32, N>>::uninit();
+        unsafe {
+            for i in 0..N {
+                let lo = i32::from(self[2 * i]) * i32::from(other[2 * i]);
+                let hi = i32::from(self[2 * i + 1]) * i32::from(other[2 * i + 1]);
+                ptr::write(data.as_mut_ptr().cast::<i32>().add(i), lo.wrapping_add(hi));
+            }
+            data.assume_init()
+        }
+    }
+}
+
+impl<A: Align, const S: usize> Vector<A, u8, S> {
+    /// Alpha-blends two vectors of color channels.
+    ///
+    /// Each lane is computed as `(self * alpha + other * (255 - alpha)) / 255`, rounded to the
+    /// nearest integer. The computation is done in 16 bits internally, so it doesn't overflow.
+    /// The alpha is per lane ‒ for packed RGBA pixels, the alpha of each pixel needs to be
+    /// repeated over all four of its channels (eg. by a [`gather_load`][Vector::gather_load]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let src = u8x4::new([255, 0, 100, 255]);
+    /// let dst = u8x4::new([0, 255, 200, 255]);
+    /// let alpha = u8x4::splat(128);
+    /// assert_eq!(src.blend_alpha(dst, alpha), u8x4::new([128, 127, 150, 255]));
+    /// ```
+    #[inline]
+    pub fn blend_alpha(self, other: Self, alpha: Self) -> Self {
+        let mut result = self;
+        for ((res, dst), alpha) in result.data.iter_mut().zip(&other.data).zip(&alpha.data) {
+            let alpha = u16::from(*alpha);
+            let t = u16::from(*res) * alpha + u16::from(*dst) * (255 - alpha) + 128;
+            // Exact rounded division by 255 for the whole range
+            *res = ((t + (t >> 8)) >> 8) as u8;
+        }
+        result
+    }
+
+    /// Sums the absolute differences of the lanes.
+    ///
+    /// The lanes are split into groups of 8 consecutive ones, and each group produces one partial
+    /// sum of `|self[i] - other[i]|` in the corresponding lane of the result (this is what the
+    /// `psadbw` instruction does). The partial sums are in 64 bits, so they can be further
+    /// accumulated across many vectors without overflowing. Use
+    /// [`horizontal_sum`][Vector::horizontal_sum] on the result to get the total.
+    ///
+    /// The number of lanes of the result must be exactly an eighth of the input (checked at
+    /// compile time).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use slipstream::prelude::*;
+    /// let a = u8x16::new([0, 10, 20, 30, 40, 50, 60, 70, 255, 0, 0, 0, 0, 0, 0, 0]);
+    /// let b = u8x16::new([5, 5, 25, 25, 45, 45, 65, 65, 0, 255, 0, 0, 0, 0, 0, 0]);
+    /// let r: u64x2 = a.sad(b);
+    /// assert_eq!(r, u64x2::new([40, 510]));
+    /// ```
+    #[inline]
+    pub fn sad<A2: Align, const N: usize>(self, other: Self) -> Vector<A2, u64, N> {
+        #[allow(clippy::let_unit_value)]
+        let () = SizeCheck::<S, N, 8>::CHUNKS;
+        Vector::<A2, u64, N>::assert_size();
+        let mut data = MaybeUninit::<Vector<A2, u64, N>>::uninit();
+        unsafe {
+            for i in 0..N {
+                let sum = (8 * i..8 * i + 8)
+                    .map(|j| u64::from(self[j].abs_diff(other[j])))
+                    .sum();
+                ptr::write(data.as_mut_ptr().cast::<u64>().add(i), sum);
+            }
+            data.assume_init()
+        }
+    }
+}
+
+impl<A: Align, B: Repr, const S: usize> Masked for Vector<A, B, S> {
+    type Mask = Vector<A, B::Mask, S>;
+}
+
+impl<A: Align, B: Default + Repr, const S: usize> Default for Vector<A, B, S> {
+    #[inline]
+    fn default() -> Self {
+        Self::splat(Default::default())
+    }
+}
+
+impl<A: Align, B: Debug + Repr, const S: usize> Debug for Vector<A, B, S> {
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        fmt.debug_tuple("Vector").field(&self.data).finish()
+    }
+}
+
+impl<A: Align, B: Repr, const S: usize> Deref for Vector<A, B, S> {
+    type Target = [B; S];
+    #[inline]
+    fn deref(&self) -> &[B; S] {
+        &self.data
+    }
+}
+
+impl<A: Align, B: Repr, const S: usize> DerefMut for Vector<A, B, S> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [B; S] {
+        &mut self.data
+    }
+}
+
+impl<A: Align, B: Repr, const S: usize> AsRef<[B]> for Vector<A, B, S> {
     #[inline]
     fn as_ref(&self) -> &[B] {
         &self.data
     }
-}
+}
+
+impl<A: Align, B: Repr, const S: usize> AsRef<[B; S]> for Vector<A, B, S> {
+    #[inline]
+    fn as_ref(&self) -> &[B; S] {
+        &self.data
+    }
+}
+
+impl<A: Align, B: Repr, const S: usize> AsMut<[B]> for Vector<A, B, S> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [B] {
+        &mut self.data
+    }
+}
+
+impl<A: Align, B: Repr, const S: usize> AsMut<[B; S]> for Vector<A, B, S> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [B; S] {
+        &mut self.data
+    }
+}
+
+impl<A: Align, B: Repr, const S: usize> From<[B; S]> for Vector<A, B, S> {
+    #[inline]
+    fn from(data: [B; S]) -> Self {
+        Self::assert_size();
+        Self { _align: [], data }
+    }
+}
+
+impl<A: Align, B: Repr, const S: usize> From<Vector<A, B, S>> for [B; S] {
+    #[inline]
+    fn from(vector: Vector<A, B, S>) -> [B; S] {
+        vector.data
+    }
+}
+
+/// Error when creating a vector from a slice of the wrong length.
+///
+/// Returned from the [`TryFrom`] conversion of a slice to a [`Vector`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LengthError {
+    /// The number of lanes of the vector.
+    pub expected: usize,
+    /// The length of the provided slice.
+    pub actual: usize,
+}
+
+impl Display for LengthError {
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        write!(
+            fmt,
+            "Creating vector from the wrong sized slice (expected {}, got {})",
+            self.expected, self.actual,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthError {}
+
+/// Error when gathering a vector with an index out of bounds.
+///
+/// Returned from [`Vector::try_gather_load`]. Describes the first offending lane.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct GatherError {
+    /// The lane of the vector with the bad index.
+    pub lane: usize,
+    /// The offending index.
+    pub index: usize,
+    /// The length of the input.
+    pub len: usize,
+}
+
+impl Display for GatherError {
+    fn fmt(&self, fmt: &mut Formatter) -> FmtResult {
+        write!(
+            fmt,
+            "Gather out of bounds (index {} in lane {}, input length {})",
+            self.index, self.lane, self.len,
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GatherError {}
+
+/// Fallible version of [`Vector::new`].
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// use std::convert::TryFrom;
+///
+/// let data = [1, 2, 3, 4, 5];
+/// assert_eq!(u32x4::try_from(&data[..4]).unwrap(), u32x4::new([1, 2, 3, 4]));
+/// let err = u32x4::try_from(&data[..]).unwrap_err();
+/// assert_eq!((err.expected, err.actual), (4, 5));
+/// ```
+impl<A: Align, B: Repr, const S: usize> TryFrom<&[B]> for Vector<A, B, S> {
+    type Error = LengthError;
+    #[inline]
+    fn try_from(input: &[B]) -> Result<Self, LengthError> {
+        if input.len() == S {
+            Ok(Self::new(input))
+        } else {
+            Err(LengthError {
+                expected: S,
+                actual: input.len(),
+            })
+        }
+    }
+}
+
+impl<I, A, B, const S: usize> Index<I> for Vector<A, B, S>
+where
+    A: Align,
+    B: Repr,
+    [B; S]: Index<I>,
+{
+    type Output = <[B; S] as Index<I>>::Output;
+    #[inline]
+    fn index(&self, idx: I) -> &Self::Output {
+        &self.data[idx]
+    }
+}
+
+impl<I, A, B, const S: usize> IndexMut<I> for Vector<A, B, S>
+where
+    A: Align,
+    B: Repr,
+    [B; S]: IndexMut<I>,
+{
+    #[inline]
+    fn index_mut(&mut self, idx: I) -> &mut Self::Output {
+        &mut self.data[idx]
+    }
+}
+
+impl<A: Align, B: AddAssign + Default + Repr, const S: usize> Sum for Vector<A, B, S> {
+    #[inline]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        let mut result = Self::default();
+        for i in iter {
+            result += i;
+        }
+
+        result
+    }
+}
+
+impl<A: Align, B: MulAssign + Repr, const S: usize> Product for Vector<A, B, S> {
+    #[inline]
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = Self>,
+    {
+        let mut result = Self::splat(B::ONE);
+        for i in iter {
+            result *= i;
+        }
+
+        result
+    }
+}
+
+/// Sums vectors behind references, like the primitive types do.
+///
+/// ```rust
+/// # use slipstream::prelude::*;
+/// let vecs = vec![f32x2::new([1.0, 2.0]), f32x2::new([3.0, 4.0])];
+/// assert_eq!(vecs.iter().sum::<f32x2>(), f32x2::new([4.0, 6.0]));
+/// ```
+impl<'a, A: Align, B: AddAssign + Default + Repr, const S: usize> Sum<&'a Self>
+    for Vector<A, B, S>
+{
+    #[inline]
+    fn sum<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Self>,
+    {
+        iter.copied().sum()
+    }
+}
+
+/// Multiplies vectors behind references, like the primitive types do.
+impl<'a, A: Align, B: MulAssign + Repr, const S: usize> Product<&'a Self> for Vector<A, B, S> {
+    #[inline]
+    fn product<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = &'a Self>,
+    {
+        iter.copied().product()
+    }
+}
+
+bin_op_impl!(Add, add, AddAssign, add_assign);
+bin_op_impl!(Sub, sub, SubAssign, sub_assign);
+bin_op_impl!(Mul, mul, MulAssign, mul_assign);
+bin_op_impl!(Div, div, DivAssign, div_assign);
+bin_op_impl!(Rem, rem, RemAssign, rem_assign);
+bin_op_impl!(BitAnd, bitand, BitAndAssign, bitand_assign);
+bin_op_impl!(BitOr, bitor, BitOrAssign, bitor_assign);
+bin_op_impl!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+bin_op_impl!(Shl, shl, ShlAssign, shl_assign);
+bin_op_impl!(Shr, shr, ShrAssign, shr_assign);
+
+una_op_impl!(Neg, neg);
+una_op_impl!(Not, not);
+
+impl<A: Align, B: PartialEq + Repr, const S: usize> PartialEq for Vector<A, B, S> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+impl<A: Align, B: Eq + Repr, const S: usize> Eq for Vector<A, B, S> {}
+
+impl<A: Align, B: PartialEq + Repr, const S: usize> PartialEq<[B; S]> for Vector<A, B, S> {
+    #[inline]
+    fn eq(&self, other: &[B; S]) -> bool {
+        self.data == *other
+    }
+}
+
+impl<A: Align, B: PartialEq + Repr, const S: usize> PartialEq<Vector<A, B, S>> for [B; S] {
+    #[inline]
+    fn eq(&self, other: &Vector<A, B, S>) -> bool {
+        *self == other.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::align::{Align1, Align16, Align8};
+    use super::*;
+    use crate::prelude::*;
+
+    type V = u16x4;
+
+    #[test]
+    #[should_panic(expected = "Creating vector from the wrong sized slice (expected 4, got 3)")]
+    fn wrong_size_new() {
+        V::new([1, 2, 3]);
+    }
+
+    #[test]
+    fn round_trip() {
+        let orig = [1, 2, 3, 4];
+        assert_eq!(<[u16; 4]>::from(u16x4::from(orig)), orig);
+    }
+
+    #[test]
+    fn ptr_aligned() {
+        let mut v = f32x8::default();
+        assert_eq!(v.as_ptr() as usize % mem::align_of::<f32x8>(), 0);
+        assert_eq!(mem::align_of::<f32x8>(), 32);
+        unsafe { *v.as_mut_ptr().add(3) = 1.0 };
+        assert_eq!(v, f32x8::new([0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Sub-vector out of range (7..9 of 8)")]
+    fn sub_vector_oob() {
+        let _: u32x2 = u32x8::default().sub_vector(7);
+    }
+
+    #[test]
+    fn split_chunks() {
+        let v = u32x8::new([0, 1, 2, 3, 4, 5, 6, 7]);
+        let [lo, hi]: [u32x4; 2] = v.split_chunks();
+        assert_eq!(lo, u32x4::new([0, 1, 2, 3]));
+        assert_eq!(hi, u32x4::new([4, 5, 6, 7]));
+        let pairs: [u32x2; 4] = v.split_chunks();
+        assert_eq!(pairs[3], u32x2::new([6, 7]));
+    }
+
+    #[test]
+    fn gather_strided() {
+        let rgba = (0..32).collect::<Vec<u8>>();
+        let green = u8x4::gather_strided::<4>(&rgba, 1);
+        assert_eq!(green, u8x4::new([1, 5, 9, 13]));
+        let alpha = u8x8::gather_strided::<4>(&rgba, 3);
+        assert_eq!(alpha, u8x8::new([3, 7, 11, 15, 19, 23, 27, 31]));
+        let same = u8x2::gather_strided::<0>(&rgba, 31);
+        assert_eq!(same, u8x2::splat(31));
+    }
+
+    #[test]
+    #[should_panic(expected = "Gather out of bounds")]
+    fn gather_strided_oob() {
+        let rgba = [0u8; 16];
+        u8x4::gather_strided::<4>(&rgba, 4);
+    }
+
+    #[test]
+    fn deinterleave() {
+        let xy = (0..16).map(|i| i as f32).collect::<Vec<_>>();
+        let [xs, ys] = f32x8::deinterleave::<2>(&xy);
+        assert_eq!(xs, f32x8::new([0.0, 2.0, 4.0, 6.0, 8.0, 10.0, 12.0, 14.0]));
+        assert_eq!(ys, f32x8::new([1.0, 3.0, 5.0, 7.0, 9.0, 11.0, 13.0, 15.0]));
+
+        let [r, g, b] = u8x2::deinterleave::<3>(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            (r, g, b),
+            (u8x2::new([1, 4]), u8x2::new([2, 5]), u8x2::new([3, 6]))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Deinterleaving wrong sized slice (expected 6, got 5)")]
+    fn deinterleave_wrong_len() {
+        u8x2::deinterleave::<3>(&[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn bytes() {
+        let mut v = u32x4::new([1, 2, 3, 4]);
+        assert_eq!(v.as_bytes().len(), 16);
+        assert_eq!(&v.as_bytes()[4..8], &2u32.to_ne_bytes());
+        v.as_bytes_mut()[12..].copy_from_slice(&5u32.to_ne_bytes());
+        assert_eq!(v, u32x4::new([1, 2, 3, 5]));
+        // Unaligned source
+        let mut buffer = [0u8; 17];
+        buffer[1..].copy_from_slice(v.as_bytes());
+        assert_eq!(u32x4::from_bytes(&buffer[1..]), v);
+    }
+
+    // The alignment padding must not be part of the bytes (it's uninitialized).
+    #[test]
+    fn bytes_padded() {
+        let mut v = Vector::<Align16, u8, 2> {
+            _align: [],
+            data: [1, 2],
+        };
+        assert_eq!(v.as_bytes(), [1, 2]);
+        assert_eq!(v.as_bytes_mut().len(), 2);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Creating vector from the wrong number of bytes (expected 16, got 15)"
+    )]
+    fn bytes_wrong_len() {
+        u32x4::from_bytes(&[0; 15]);
+    }
+
+    #[test]
+    fn shuffle() {
+        let v1 = V::new([1, 2, 3, 4]);
+        let v2 = V::gather_load(v1, [3, 1, 2, 0]);
+        assert_eq!(v2.deref(), &[4, 2, 3, 1]);
+        let v3 = V::gather_load(v2, [0, 0, 2, 2]);
+        assert_eq!(v3.deref(), &[4, 4, 3, 3]);
+    }
+
+    #[test]
+    fn gather() {
+        let data = (1..=10).collect::<Vec<_>>();
+        let v = V::gather_load(data, [0, 2, 4, 6]);
+        assert_eq!(v, [1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn scatter() {
+        let v = V::new([1, 2, 3, 4]);
+        let mut output = [0; 10];
+        v.scatter_store(&mut output, [1, 3, 5, 7]);
+        assert_eq!(output, [0, 1, 0, 2, 0, 3, 0, 4, 0, 0]);
+    }
+
+    #[test]
+    fn blend_bitmask() {
+        let a = V::new([1, 2, 3, 4]);
+        let b = V::new([5, 6, 7, 8]);
+        assert_eq!(a.blend_bitmask(b, 0), a);
+        assert_eq!(a.blend_bitmask(b, 0b1111), b);
+        assert_eq!(a.blend_bitmask(b, !0b0110), V::new([5, 2, 3, 8]));
+        let mask = a.gt(V::splat(2));
+        assert_eq!(a.blend_bitmask(b, mask.to_bitmask()), a.blend(b, mask));
+    }
+
+    #[test]
+    fn neg_masked() {
+        let v = i32x4::new([1, -2, 3, i32::MIN + 1]);
+        let mask = m32x4::new([m32::FALSE, m32::TRUE, m32::FALSE, m32::TRUE]);
+        assert_eq!(v.neg_masked(mask), i32x4::new([1, 2, 3, i32::MAX]));
+        assert_eq!(v.neg_masked(mask), v.blend(-v, mask));
+        assert_eq!(v.neg_masked([false; 4]), v);
+    }
+
+    #[test]
+    fn extend_from() {
+        // 4-bit fields in the low nibble, garbage in the high one
+        let v = u8x4::new([0x07, 0x08, 0xAF, 0x31]);
+        assert_eq!(
+            v.sign_extend_from::<4>(),
+            u8x4::new([0x07, 0xF8, 0xFF, 0x01])
+        );
+        assert_eq!(
+            v.zero_extend_from::<4>(),
+            u8x4::new([0x07, 0x08, 0x0F, 0x01])
+        );
+        assert_eq!(v.sign_extend_from::<8>(), v);
+        assert_eq!(v.zero_extend_from::<8>(), v);
+        assert_eq!(v.sign_extend_from::<1>(), u8x4::new([0xFF, 0, 0xFF, 0xFF]));
+
+        let s = i8x4::new([0x07, 0x08, -0x51, 0x31]);
+        assert_eq!(s.sign_extend_from::<4>(), i8x4::new([7, -8, -1, 1]));
+        assert_eq!(s.zero_extend_from::<4>(), i8x4::new([7, 8, 15, 1]));
+    }
+
+    #[test]
+    fn horizontal_sum_ordered() {
+        let data = [0.1, 1e-8, 0.3, -0.2, 1e-9, 0.7, 3.0, -1e-8];
+        let v = f32x8::new(data);
+        let scalar = data.iter().fold(0.0, |acc, &x| acc + x);
+        let ordered = v.horizontal_sum_ordered();
+        assert_eq!(ordered.to_bits(), scalar.to_bits());
+
+        // Differs in the last bit depending on the order
+        let v = f32x4::new([1.0, 4e-8, 4e-8, 4e-8]);
+        assert_eq!(v.horizontal_sum_ordered(), 1.0);
+        assert_eq!(v.horizontal_sum(), 1.0 + f32::EPSILON);
+        assert_ne!(v.horizontal_sum_ordered(), v.horizontal_sum());
+
+        assert_eq!(V::new([1, 2, 3, 4]).horizontal_sum_ordered(), 10);
+    }
+
+    #[test]
+    fn try_from_array_ref() {
+        use core::convert::TryInto;
+
+        let data = [u32x4::new([0, 1, 2, 3]), u32x4::new([4, 5, 6, 7])];
+        let flat = unsafe { core::slice::from_raw_parts(data.as_ptr().cast::<u32>(), 8) };
+
+        let aligned: &[u32; 4] = flat[4..].try_into().unwrap();
+        let v = u32x4::try_from_array_ref(aligned).unwrap();
+        assert_eq!(v, &data[1]);
+        assert_eq!(v.as_ptr(), aligned.as_ptr());
+
+        for start in 1..4 {
+            let misaligned: &[u32; 4] = flat[start..start + 4].try_into().unwrap();
+            assert!(u32x4::try_from_array_ref(misaligned).is_none());
+        }
+
+        // No alignment requirement beyond the base type, always succeeds
+        let unaligned = [1u32, 2, 3, 4];
+        let v = Vector::<Align1, u32, 4>::try_from_array_ref(&unaligned).unwrap();
+        assert_eq!(*v, unaligned);
+    }
+
+    #[test]
+    fn swap_lanes() {
+        let v = V::new([1, 2, 3, 4]);
+        assert_eq!(v.swap_lanes(1, 2), V::new([1, 3, 2, 4]));
+        assert_eq!(v.swap_lanes(3, 3), v);
+        assert_eq!(v.swap_lanes(0, 3).swap_lanes(3, 0), v);
+    }
+
+    #[test]
+    #[should_panic(expected = "Swapping lanes 1 and 4 out of 4")]
+    fn swap_lanes_oob() {
+        V::new([1, 2, 3, 4]).swap_lanes(1, 4);
+    }
+
+    #[test]
+    fn leading_trailing_eq() {
+        let chunk = u8x16::new(*b"   let x = 1;   ");
+        assert_eq!(chunk.leading_eq(b' '), 3);
+        assert_eq!(chunk.trailing_eq(b' '), 3);
+        assert_eq!(chunk.leading_eq(b';'), 0);
+
+        let spaces = u8x16::splat(b' ');
+        assert_eq!(spaces.leading_eq(b' '), 16);
+        assert_eq!(spaces.trailing_eq(b' '), 16);
+
+        let full = u8x32::splat(0);
+        assert_eq!(full.leading_eq(0), 32);
+        assert_eq!(full.trailing_eq(0), 32);
+        assert_eq!(full.trailing_eq(1), 0);
+
+        let single = Vector::<Align1, u8, 1>::new([7]);
+        assert_eq!(single.leading_eq(7), 1);
+        assert_eq!(single.trailing_eq(7), 1);
+        assert_eq!(single.trailing_eq(0), 0);
+    }
+
+    #[test]
+    fn masked_horizontal_sum() {
+        let v = f32x4::new([1.5, 2.0, -3.0, 4.25]);
+        let mask = m32x4::new([m32::TRUE, m32::FALSE, m32::FALSE, m32::TRUE]);
+        assert_eq!(v.masked_horizontal_sum(mask), 5.75);
+        assert_eq!(v.masked_horizontal_sum([false; 4]), 0.0);
+        assert_eq!(v.masked_horizontal_sum([true; 4]), v.horizontal_sum());
+    }
+
+    #[test]
+    fn saturating_horizontal_sum() {
+        let v = u8x16::splat(20);
+        assert_eq!(v.saturating_horizontal_sum(), u8::MAX);
+        assert_eq!(u8x4::new([1, 2, 3, 4]).saturating_horizontal_sum(), 10);
+        assert_eq!(u8x4::new([250, 5, 0, 0]).saturating_horizontal_sum(), 255);
+        assert_eq!(u8x4::new([250, 6, 0, 0]).saturating_horizontal_sum(), 255);
+
+        let s = i8x4::new([-100, -100, 50, 1]);
+        assert_eq!(s.saturating_horizontal_sum(), -128 + 51);
+    }
+
+    #[test]
+    fn checked_div_rem() {
+        let a = V::new([10, 11, 12, 13]);
+        assert_eq!(a.checked_div(V::splat(3)), Some(a / V::splat(3)));
+        assert_eq!(a.checked_rem(V::splat(3)), Some(a % V::splat(3)));
+        let zero_lane = V::new([1, 2, 0, 4]);
+        assert_eq!(a.checked_div(zero_lane), None);
+        assert_eq!(a.checked_rem(zero_lane), None);
+
+        let min = i32x2::new([i32::MIN, 1]);
+        assert_eq!(min.checked_div(i32x2::splat(-1)), None);
+        assert_eq!(min.checked_rem(i32x2::splat(-1)), None);
+        assert_eq!(
+            min.checked_div(i32x2::splat(1)),
+            Some(i32x2::new([i32::MIN, 1]))
+        );
+    }
+
+    #[test]
+    fn try_gather() {
+        let data = [1, 2, 3, 4, 5];
+        assert_eq!(
+            V::try_gather_load(data, [4, 3, 0, 0]),
+            Ok(V::new([5, 4, 1, 1]))
+        );
+        let err = V::try_gather_load(data, [0, 7, 2, 9]).unwrap_err();
+        assert_eq!(
+            err,
+            GatherError {
+                lane: 1,
+                index: 7,
+                len: 5,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Gather out of bounds (index 7 in lane 1, input length 5)"
+        );
+    }
+
+    // Indices computed in vectors can be used directly, without converting to arrays.
+    #[test]
+    fn scatter_vector_idx() {
+        let idx = usizex4::new([0, 1, 2, 3]) * usizex4::splat(2) + usizex4::splat(1);
+        let mut output = [0; 10];
+        V::new([1, 2, 3, 4]).scatter_store(&mut output, idx);
+        assert_eq!(output, [0, 1, 0, 2, 0, 3, 0, 4, 0, 0]);
+        assert_eq!(V::gather_load(output, idx), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Gather out of bounds")]
+    fn gather_oob() {
+        V::gather_load([1, 2, 3], [0, 1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Gathering vector from wrong number of indexes")]
+    fn gather_idx_cnt() {
+        V::gather_load([0, 1, 2, 3, 4], [0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Scatter out of bounds")]
+    fn scatter_oob() {
+        let mut out = [0; 10];
+        V::new([1, 2, 3, 4]).scatter_store(&mut out, [0, 1, 2, 15]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Scattering vector to wrong number of indexes")]
+    fn scatter_idx_cnt() {
+        let mut out = [0; 10];
+        V::new([1, 2, 3, 4]).scatter_store(&mut out, [0, 1, 2]);
+    }
+
+    const T: m32 = m32::TRUE;
+    const F: m32 = m32::FALSE;
+
+    #[test]
+    #[should_panic(expected = "Gather out of bounds")]
+    fn gather_masked_oob() {
+        V::default().gather_load_masked([1, 2, 3], [0, 1, 2, 3], [T, F, F, T]);
+    }
+
+    #[test]
+    fn gather_masked_oob_disabled() {
+        let v = V::default().gather_load_masked([1, 2, 3], [0, 1, 2, 3], [T, F, T, F]);
+        assert_eq!(v, [1, 0, 3, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Scatter out of bounds")]
+    fn scatter_masked_oob() {
+        let mut out = [0; 3];
+        V::new([1, 2, 3, 4]).scatter_store_masked(&mut out, [0, 1, 2, 3], [T, F, F, T]);
+    }
+
+    #[test]
+    fn scatter_masked_oob_disabled() {
+        let mut out = [0; 3];
+        V::new([1, 2, 3, 4]).scatter_store_masked(&mut out, [0, 1, 2, 3], [T, F, T, F]);
+        assert_eq!(out, [1, 0, 3]);
+    }
+
+    #[test]
+    fn load_masked_tail() {
+        let tail = [1, 2, 3];
+        let v = u32x4::splat(10).load_masked(tail, m32x4::new([T, T, T, F]));
+        assert_eq!(v, u32x4::new([1, 2, 3, 10]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Load out of bounds")]
+    fn load_masked_oob() {
+        u32x4::default().load_masked([1, 2, 3], m32x4::new([T, T, T, T]));
+    }
+
+    #[test]
+    fn volatile() {
+        let mut buffer = [0u16; 6];
+        let v = V::new([1, 2, 3, 4]);
+        unsafe { v.store_volatile(buffer[1..].as_mut_ptr()) };
+        assert_eq!(buffer, [0, 1, 2, 3, 4, 0]);
+        let loaded = unsafe { V::load_volatile(buffer[2..].as_ptr()) };
+        assert_eq!(loaded, V::new([2, 3, 4, 0]));
+    }
+
+    #[test]
+    fn store_masked() {
+        let v = u32x4::new([1, 2, 3, 4]);
+        let mut output = [10; 6];
+        v.store_masked(&mut output, m32x4::new([T, F, F, T]));
+        assert_eq!(output, [1, 10, 10, 4, 10, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Store out of bounds")]
+    fn store_masked_oob() {
+        let mut output = [10; 3];
+        u32x4::new([1, 2, 3, 4]).store_masked(&mut output, m32x4::new([T, F, F, T]));
+    }
+
+    #[test]
+    fn cmp() {
+        let v1 = u32x4::new([1, 3, 5, 7]);
+        let v2 = u32x4::new([2, 3, 4, 5]);
+
+        assert_eq!(v1.eq(v2), m32x4::new([F, T, F, F]));
+        assert_eq!(v1.le(v2), m32x4::new([T, T, F, F]));
+        assert_eq!(v1.ge(v2), m32x4::new([F, T, T, T]));
+    }
+
+    #[test]
+    fn blend() {
+        let v1 = u32x4::new([1, 2, 3, 4]);
+        let v2 = u32x4::new([5, 6, 7, 8]);
+
+        let b1 = v1.blend(v2, m32x4::new([F, T, F, T]));
+        assert_eq!(b1, u32x4::new([1, 6, 3, 8]));
+
+        let b2 = v1.blend(v2, [false, true, false, true]);
+        assert_eq!(b1, b2);
+    }
+
+    #[test]
+    fn compress_expand() {
+        let v = u32x4::new([10, 20, 30, 40]);
+        let mask = m32x4::new([F, T, F, T]);
+        let (packed, cnt) = v.compress(mask);
+        assert_eq!(cnt, 2);
+        assert_eq!(&packed[..cnt], &[20, 40]);
+
+        let restored = u32x4::default().expand(packed, mask);
+        assert_eq!(restored, u32x4::new([0, 20, 0, 40]));
+
+        let (all, cnt) = v.compress(m32x4::splat(T));
+        assert_eq!((all, cnt), (v, 4));
+        let (_, cnt) = v.compress(m32x4::splat(F));
+        assert_eq!(cnt, 0);
+    }
+
+    fn check_sorted<B, const S: usize>(data: [B; S])
+    where
+        B: Repr + Ord + Debug,
+    {
+        let v = Vector::<Align1, B, S>::new(data);
+        let mut expected = data;
+        expected.sort();
+        assert_eq!(v.sort_lanes(), expected);
+    }
+
+    proptest! {
+        #[test]
+        fn sort_lanes_2(data: [i32; 2]) {
+            check_sorted(data);
+        }
+
+        #[test]
+        fn sort_lanes_4(data: [u8; 4]) {
+            check_sorted(data);
+        }
+
+        #[test]
+        fn sort_lanes_8(data: [i16; 8]) {
+            check_sorted(data);
+        }
+
+        #[test]
+        fn sort_lanes_16(data: [u32; 16]) {
+            check_sorted(data);
+        }
+
+        #[test]
+        fn sort_lanes_odd(data: [i64; 5]) {
+            check_sorted(data);
+        }
+    }
+
+    #[test]
+    fn sort_lanes_float() {
+        let v = f32x4::new([2.0, -0.5, 8.0, 1.0]);
+        assert_eq!(v.sort_lanes(), f32x4::new([-0.5, 1.0, 2.0, 8.0]));
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(f32x4::new([4.0, 1.0, 3.0, 2.0]).median(), 2.5);
+        assert_eq!(f64x2::new([-1.0, 1.0]).median(), 0.0);
+        assert_eq!(i32x4::new([4, -1, 3, 2]).median(), 2);
+        let odd = Vector::<Align1, u8, 5>::new([9, 1, 7, 3, 5]);
+        assert_eq!(odd.median(), 5);
+        // The middle of the extremes must not overflow to infinity.
+        assert_eq!(f32x2::new([-f32::MAX, f32::MAX]).median(), 0.0);
+        assert_eq!(f64x2::new([f64::MAX, f64::MAX]).median(), f64::MAX);
+    }
+
+    #[test]
+    fn is_zero() {
+        let v = f32x4::new([0.0, -0.0, 1.0, f32::NAN]);
+        assert_eq!(v.is_zero(), m32x4::new([T, T, F, F]));
+        assert_eq!(v.is_nonzero(), m32x4::new([F, F, T, T]));
+    }
+
+    #[test]
+    fn try_from_slice() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let v = V::try_from(&data[2..]).unwrap();
+        assert_eq!(v, V::new([3, 4, 5, 6]));
+
+        let err = V::try_from(&data[..3]).unwrap_err();
+        assert_eq!(
+            err,
+            LengthError {
+                expected: 4,
+                actual: 3,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Creating vector from the wrong sized slice (expected 4, got 3)"
+        );
+    }
+
+    #[test]
+    fn wrapping_shifts() {
+        let amounts = u32x4::new([0, 31, 32, 40]);
+        let v = u32x4::splat(0x8000_0001);
+        assert_eq!(
+            v.wrapping_shl(amounts),
+            u32x4::new([0x8000_0001, 0x8000_0000, 0x8000_0001, 0x100])
+        );
+        assert_eq!(
+            v.wrapping_shr(amounts),
+            u32x4::new([0x8000_0001, 1, 0x8000_0001, 0x80_0000])
+        );
+
+        // Negative amounts are masked too and the right shift keeps the sign
+        let v = i8x4::splat(-128);
+        let amounts = i8x4::new([-1, 7, 8, 9]);
+        assert_eq!(v.wrapping_shr(amounts), i8x4::new([-1, -1, -128, -64]));
+        assert_eq!(v.wrapping_shl(amounts), i8x4::new([0, 0, -128, 0]));
+    }
+
+    #[test]
+    fn div_rounding() {
+        let a = i32x4::new([-7, 7, -7, 6]);
+        let b = i32x4::new([2, 2, -2, -3]);
+        assert_eq!(a.div_floor(b), i32x4::new([-4, 3, 3, -2]));
+        assert_eq!(a.div_ceil(b), i32x4::new([-3, 4, 4, -2]));
+
+        let a = u32x4::new([0, 1, 8, 9]);
+        assert_eq!(a.div_floor(u32x4::splat(4)), u32x4::new([0, 0, 2, 2]));
+        assert_eq!(a.div_ceil(u32x4::splat(4)), u32x4::new([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn powers_of_two() {
+        let v = u8x4::new([0, 1, 127, 128]);
+        assert_eq!(v.next_power_of_two(), u8x4::new([1, 1, 128, 128]));
+        let v = u64x4::new([2, 3, u64::MAX, 1 << 40]);
+        assert_eq!(v.ilog2(), u64x4::new([1, 1, 63, 40]));
+    }
+
+    #[test]
+    #[should_panic(expected = "argument of integer logarithm must be positive")]
+    fn ilog2_zero() {
+        u32x4::new([1, 0, 2, 3]).ilog2();
+    }
+
+    #[test]
+    fn madd() {
+        let a = i16x8::new([1, -1, 300, 300, i16::MAX, i16::MAX, i16::MIN, i16::MIN]);
+        let b = i16x8::new([2, 3, 400, -400, i16::MAX, i16::MAX, i16::MIN, i16::MIN]);
+        let r: i32x4 = a.madd(b);
+        assert_eq!(r, i32x4::new([-1, 0, 2 * 32767 * 32767, i32::MIN]));
+    }
+
+    #[test]
+    fn sad() {
+        let a = (0..32u32).map(|i| (i * 37 % 256) as u8).collect::<Vec<_>>();
+        let b = (0..32u32).map(|i| (i * 91 % 256) as u8).collect::<Vec<_>>();
+        let expected = a
+            .chunks(8)
+            .zip(b.chunks(8))
+            .map(|(a, b)| {
+                a.iter()
+                    .zip(b)
+                    .map(|(&a, &b)| (i64::from(a) - i64::from(b)).unsigned_abs())
+                    .sum::<u64>()
+            })
+            .collect::<Vec<_>>();
+        let r: u64x4 = u8x32::new(&a).sad(u8x32::new(&b));
+        assert_eq!(r, u64x4::new(&expected));
+        let r: Vector<Align8, u64, 1> = u8x8::new(&a[..8]).sad(u8x8::new(&b[..8]));
+        assert_eq!(r[0], expected[0]);
+    }
+
+    #[test]
+    fn count_eq() {
+        let v = u8x16::new([1, 2, 3, 1, 0, 1, 255, 2, 1, 1, 7, 8, 9, 0, 1, 3]);
+        assert_eq!(v.count_eq(1), 6);
+        assert_eq!(v.count_eq(0), 2);
+        assert_eq!(v.count_eq(255), 1);
+        assert_eq!(v.count_eq(42), 0);
+        assert_eq!(u8x16::splat(42).count_eq(42), 16);
+    }
 
-impl<A: Align, B: Repr, const S: usize> AsRef<[B; S]> for Vector<A, B, S> {
-    #[inline]
-    fn as_ref(&self) -> &[B; S] {
-        &self.data
+    #[test]
+    fn cmp_masks() {
+        let a = u32x4::new([1, 5, 3, 7]);
+        let b = u32x4::new([2, 5, 1, 9]);
+        assert_eq!(a.cmp_eq_mask(b), 0b0010);
+        assert_eq!(a.cmp_ne_mask(b), 0b1101);
+        assert_eq!(a.cmp_lt_mask(b), 0b1001);
+        assert_eq!(a.cmp_le_mask(b), 0b1011);
+        assert_eq!(a.cmp_gt_mask(b), 0b0100);
+        assert_eq!(a.cmp_ge_mask(b), 0b0110);
+        assert_eq!(a.lt(b).to_bitmask(), a.cmp_lt_mask(b));
+        assert_eq!(u8x32::splat(1).cmp_eq_mask(u8x32::splat(1)), u32::MAX);
     }
-}
 
-impl<A: Align, B: Repr, const S: usize> AsMut<[B]> for Vector<A, B, S> {
-    #[inline]
-    fn as_mut(&mut self) -> &mut [B] {
-        &mut self.data
+    #[test]
+    fn blend_alpha() {
+        // Two RGBA pixels over another two, first opaque, second transparent, then half.
+        let src = u8x16::new([
+            10, 20, 30, 255, 40, 50, 60, 0, 255, 255, 255, 128, 0, 0, 0, 128,
+        ]);
+        let dst = u8x16::new([
+            200, 100, 0, 255, 1, 2, 3, 255, 0, 0, 0, 255, 255, 255, 255, 255,
+        ]);
+        let alpha = u8x16::new([
+            255, 255, 255, 255, 0, 0, 0, 0, 128, 128, 128, 128, 128, 128, 128, 128,
+        ]);
+        let r = src.blend_alpha(dst, alpha);
+        assert_eq!(&r[..4], &src[..4]);
+        assert_eq!(&r[4..8], &dst[4..8]);
+        assert_eq!(&r[8..11], &[128, 128, 128]);
+        assert_eq!(&r[12..15], &[127, 127, 127]);
+
+        // Against the floating point reference
+        for s in (0..=255).step_by(5) {
+            for d in (0..=255).step_by(3) {
+                for a in 0..=255 {
+                    let expected =
+                        (f64::from(s) * f64::from(a) + f64::from(d) * f64::from(255 - a)) / 255.0;
+                    let got = u8x2::splat(s).blend_alpha(u8x2::splat(d), u8x2::splat(a))[0];
+                    assert_eq!(got, expected.round() as u8, "{} {} {}", s, d, a);
+                }
+            }
+        }
     }
-}
 
-impl<A: Align, B: Repr, const S: usize> AsMut<[B; S]> for Vector<A, B, S> {
-    #[inline]
-    fn as_mut(&mut self) -> &mut [B; S] {
-        &mut self.data
+    #[test]
+    fn normalize() {
+        let v = f32x4::new([3.0, 4.0, 0.0, 0.0]);
+        assert_eq!(v.magnitude(), 5.0);
+        assert_eq!(v.normalize(), f32x4::new([0.6, 0.8, 0.0, 0.0]));
+        for v in &[
+            f32x4::new([1.0, -2.0, 3.5, 0.1]),
+            f32x4::splat(1e-10),
+            f32x4::new([-1e10, 0.0, 0.0, 2.0]),
+        ] {
+            assert!((v.normalize().magnitude() - 1.0).abs() < 1e-6, "{:?}", v);
+        }
+        assert_eq!(f32x4::default().normalize(), f32x4::default());
     }
-}
 
-impl<A: Align, B: Repr, const S: usize> From<[B; S]> for Vector<A, B, S> {
-    #[inline]
-    fn from(data: [B; S]) -> Self {
-        Self::assert_size();
-        Self { _align: [], data }
+    #[test]
+    fn cross3() {
+        let x = f32x4::new([1.0, 0.0, 0.0, 7.0]);
+        let y = f32x4::new([0.0, 1.0, 0.0, f32::NAN]);
+        let z = f32x4::new([0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(x.cross3(y), z);
+        assert_eq!(y.cross3(x), -z);
+        assert_eq!(y.cross3(z), f32x4::new([1.0, 0.0, 0.0, 0.0]));
+        assert_eq!(z.cross3(x), f32x4::new([0.0, 1.0, 0.0, 0.0]));
+        assert_eq!(x.cross3(x), f32x4::default());
+
+        let a = f64x4::new([1.0, 2.0, 3.0, 0.0]);
+        let b = f64x4::new([-4.0, 5.0, 0.5, 0.0]);
+        let c = a.cross3(b);
+        assert_eq!(c, f64x4::new([-14.0, -12.5, 13.0, 0.0]));
+        assert_eq!(c.dot(a), 0.0);
+        assert_eq!(c.dot(b), 0.0);
     }
-}
 
-impl<A: Align, B: Repr, const S: usize> From<Vector<A, B, S>> for [B; S] {
-    #[inline]
-    fn from(vector: Vector<A, B, S>) -> [B; S] {
-        vector.data
+    #[test]
+    fn consts() {
+        assert_eq!(u32x4::ONE, u32x4::splat(1));
+        assert_eq!(u32x4::ZERO, u32x4::default());
+        assert_eq!(wi8x16::ONE, wi8x16::splat(core::num::Wrapping(1)));
+        assert_eq!(f64x2::ZERO, f64x2::splat(0.0));
+        assert_eq!(m32x4::ONE, m32x4::splat(m32::TRUE));
+        assert_eq!(m32x4::ZERO, m32x4::splat(m32::FALSE));
+        const DOUBLE: u16x4 = V::ONE;
+        assert_eq!(DOUBLE + DOUBLE, V::splat(2));
     }
-}
 
-impl<I, A, B, const S: usize> Index<I> for Vector<A, B, S>
-where
-    A: Align,
-    B: Repr,
-    [B; S]: Index<I>,
-{
-    type Output = <[B; S] as Index<I>>::Output;
-    #[inline]
-    fn index(&self, idx: I) -> &Self::Output {
-        &self.data[idx]
+    #[test]
+    fn nan_finite_checks() {
+        let clean = f64x4::new([0.0, -1.5, 1e300, f64::MIN_POSITIVE]);
+        assert!(!clean.any_nan());
+        assert!(clean.all_finite());
+
+        for &bad in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            for i in 0..4 {
+                let mut v = clean;
+                v[i] = bad;
+                assert_eq!(v.any_nan(), bad.is_nan());
+                assert!(!v.all_finite());
+            }
+        }
     }
-}
 
-impl<I, A, B, const S: usize> IndexMut<I> for Vector<A, B, S>
-where
-    A: Align,
-    B: Repr,
-    [B; S]: IndexMut<I>,
-{
-    #[inline]
-    fn index_mut(&mut self, idx: I) -> &mut Self::Output {
-        &mut self.data[idx]
+    #[test]
+    fn approx_eq() {
+        let a = f64x4::new([1.0, 2.0, -3.0, f64::NAN]);
+        let b = f64x4::new([1.0 + 1e-12, 2.5, -3.0, f64::NAN]);
+        let eq = a.approx_eq(b, 1e-9);
+        assert_eq!(
+            eq,
+            m64x4::new([m64::TRUE, m64::FALSE, m64::TRUE, m64::FALSE])
+        );
+        assert_eq!(a.approx_eq(b, 0.5).to_bitmask(), 0b0111);
     }
-}
 
-impl<A: Align, B: AddAssign + Default + Repr, const S: usize> Sum for Vector<A, B, S> {
-    #[inline]
-    fn sum<I>(iter: I) -> Self
-    where
-        I: Iterator<Item = Self>,
-    {
-        let mut result = Self::default();
-        for i in iter {
-            result += i;
+    #[test]
+    fn total_order() {
+        let nan = f64::NAN;
+        let a = f64x4::new([nan, -0.0, 0.0, 1.0]);
+        let b = f64x4::new([1.0, 0.0, -0.0, -nan]);
+        let bits = |v: f64x4| v.iter().map(|l| l.to_bits()).collect::<Vec<_>>();
+        let min = a.min_total(b);
+        let max = a.max_total(b);
+        assert_eq!(bits(min), bits(f64x4::new([1.0, -0.0, -0.0, -nan])));
+        assert_eq!(bits(max), bits(f64x4::new([nan, 0.0, 0.0, 1.0])));
+
+        let sorted = f64x4::new([0.0, nan, 1.0, -0.0]).sort_lanes_total();
+        assert_eq!(bits(sorted), bits(f64x4::new([-0.0, 0.0, 1.0, nan])));
+        let sorted =
+            f64x8::new([nan, 2.0, -nan, 0.0, -1.0, -0.0, f64::INFINITY, 1.0]).sort_lanes_total();
+        let expected = [-nan, -1.0, -0.0, 0.0, 1.0, 2.0, f64::INFINITY, nan];
+        let expected_bits = expected.iter().map(|l| l.to_bits()).collect::<Vec<_>>();
+        let sorted_bits = sorted.iter().map(|l| l.to_bits()).collect::<Vec<_>>();
+        assert_eq!(sorted_bits, expected_bits);
+    }
+
+    #[test]
+    fn bytes_in_lane() {
+        let v = u32x2::new([0x0102_0304, 0xDEAD_BEEF]);
+        for n in 0..9 {
+            let rotated = v.rotate_bytes_in_lane(n);
+            for (r, l) in rotated.iter().zip(v.iter()) {
+                let mut bytes = l.to_le_bytes();
+                bytes.rotate_right(n as usize % 4);
+                assert_eq!(*r, u32::from_le_bytes(bytes));
+            }
         }
+        assert_eq!(
+            v.reverse_bytes_in_lane(),
+            u32x2::new([0x0403_0201, 0xEFBE_ADDE])
+        );
+        let w = i16x4::new([0x0102, -2, 0, 0x7F00]);
+        assert_eq!(w.reverse_bytes_in_lane(), w.rotate_bytes_in_lane(1));
+    }
 
-        result
+    #[test]
+    fn gray() {
+        let v = u8x16::new((0..16).collect::<Vec<_>>());
+        let gray = v.to_gray();
+        assert_eq!(gray.from_gray(), v);
+        for (g, next) in gray.iter().zip(gray.iter().skip(1)) {
+            assert_eq!((g ^ next).count_ones(), 1);
+        }
+        let w = u64x4::new([0, u64::MAX, 1 << 63, 0xDEAD_BEEF_1234_5678]);
+        assert_eq!(w.to_gray().from_gray(), w);
+        assert_eq!(w.from_gray().to_gray(), w);
     }
-}
 
-impl<A: Align, B: MulAssign + Repr, const S: usize> Product for Vector<A, B, S> {
-    #[inline]
-    fn product<I>(iter: I) -> Self
-    where
-        I: Iterator<Item = Self>,
-    {
-        let mut result = Self::splat(B::ONE);
-        for i in iter {
-            result *= i;
+    #[test]
+    fn funnel_shl() {
+        // A big-endian bit stream of 32-bit words, reading 32 bits at arbitrary bit offsets.
+        let words = [0xDEAD_BEEFu32, 0x0123_4567, 0x89AB_CDEF];
+        let hi = u32x2::new([words[0], words[1]]);
+        let lo = u32x2::new([words[1], words[2]]);
+        for offset in 0..64 {
+            let stream =
+                (u128::from(words[0]) << 64) | (u128::from(words[1]) << 32) | u128::from(words[2]);
+            let word = offset / 32;
+            let expected = (stream >> (64 - offset % 32 - 32 * word)) as u32;
+            let got = hi.funnel_shl(lo, offset as u32 % 32)[word];
+            assert_eq!(got, expected, "{}", offset);
         }
+        assert_eq!(hi.funnel_shl(lo, 32), hi);
 
-        result
+        let signed = i8x2::new([0, -1]);
+        let low = i8x2::new([-128, -128]);
+        assert_eq!(signed.funnel_shl(low, 1), i8x2::new([1, -1]));
+        assert_eq!(signed.funnel_shl(low, 7), i8x2::new([64, -64]));
     }
-}
 
-bin_op_impl!(Add, add, AddAssign, add_assign);
-bin_op_impl!(Sub, sub, SubAssign, sub_assign);
-bin_op_impl!(Mul, mul, MulAssign, mul_assign);
-bin_op_impl!(Div, div, DivAssign, div_assign);
-bin_op_impl!(Rem, rem, RemAssign, rem_assign);
-bin_op_impl!(BitAnd, bitand, BitAndAssign, bitand_assign);
-bin_op_impl!(BitOr, bitor, BitOrAssign, bitor_assign);
-bin_op_impl!(BitXor, bitxor, BitXorAssign, bitxor_assign);
-bin_op_impl!(Shl, shl, ShlAssign, shl_assign);
-bin_op_impl!(Shr, shr, ShrAssign, shr_assign);
+    #[test]
+    fn downsample_2x() {
+        let ramp = u8x8::new([0, 1, 2, 3, 4, 5, 6, 7]);
+        let half: u8x4 = ramp.downsample_2x();
+        assert_eq!(half, u8x4::new([1, 3, 5, 7]));
 
-una_op_impl!(Neg, neg);
-una_op_impl!(Not, not);
+        let ramp = u16x16::new((0..16).map(|i| i * 4000).collect::<Vec<_>>());
+        let half: u16x8 = ramp.downsample_2x();
+        for (i, h) in half.iter().enumerate() {
+            let expected = (u32::from(ramp[2 * i]) + u32::from(ramp[2 * i + 1])).div_ceil(2);
+            assert_eq!(u32::from(*h), expected);
+        }
 
-impl<A: Align, B: PartialEq + Repr, const S: usize> PartialEq for Vector<A, B, S> {
-    #[inline]
-    fn eq(&self, other: &Self) -> bool {
-        self.data == other.data
+        for a in 0..=255 {
+            for b in 0..=255 {
+                let expected = (u16::from(a) + u16::from(b)).div_ceil(2) as u8;
+                assert_eq!(u8x2::new([a, b]).avg(u8x2::new([b, a]))[0], expected);
+            }
+        }
     }
-}
 
-impl<A: Align, B: Eq + Repr, const S: usize> Eq for Vector<A, B, S> {}
+    #[test]
+    fn transform() {
+        let identity = [
+            f32x4::new([1.0, 0.0, 0.0, 0.0]),
+            f32x4::new([0.0, 1.0, 0.0, 0.0]),
+            f32x4::new([0.0, 0.0, 1.0, 0.0]),
+            f32x4::new([0.0, 0.0, 0.0, 1.0]),
+        ];
+        let point = f32x4::new([1.5, -2.0, 7.0, 1.0]);
+        assert_eq!(point.transform4(identity), point);
+        assert_eq!(
+            point.transform3([identity[0], identity[1], identity[2]]),
+            point
+        );
 
-impl<A: Align, B: PartialEq + Repr, const S: usize> PartialEq<[B; S]> for Vector<A, B, S> {
-    #[inline]
-    fn eq(&self, other: &[B; S]) -> bool {
-        self.data == *other
+        let mut translation = identity;
+        translation[0][3] = 10.0;
+        translation[1][3] = -1.0;
+        translation[2][3] = 0.5;
+        let expected = f32x4::new([11.5, -3.0, 7.5, 1.0]);
+        assert_eq!(point.transform4(translation), expected);
+        assert_eq!(
+            point.transform3([translation[0], translation[1], translation[2]]),
+            expected
+        );
+        // Directions (w = 0) are not translated
+        let direction = f32x4::new([1.0, 2.0, 3.0, 0.0]);
+        assert_eq!(direction.transform4(translation), direction);
+
+        let scale_rotate = [
+            f64x4::new([0.0, -2.0, 0.0, 0.0]),
+            f64x4::new([2.0, 0.0, 0.0, 0.0]),
+            f64x4::new([0.0, 0.0, 2.0, 0.0]),
+        ];
+        let v = f64x4::new([1.0, 2.0, 3.0, 0.0]);
+        assert_eq!(
+            v.transform3(scale_rotate),
+            f64x4::new([-4.0, 2.0, 6.0, 0.0])
+        );
     }
-}
 
-impl<A: Align, B: PartialEq + Repr, const S: usize> PartialEq<Vector<A, B, S>> for [B; S] {
-    #[inline]
-    fn eq(&self, other: &Vector<A, B, S>) -> bool {
-        *self == other.data
+    #[test]
+    fn outer() {
+        let a = f32x2::new([2.0, -1.0]);
+        let b = f32x2::new([0.5, 3.0]);
+        let m = a.outer(b);
+        assert_eq!(m[0], f32x2::new([1.0, 6.0]));
+        assert_eq!(m[1], f32x2::new([-0.5, -3.0]));
+        assert_eq!(b.outer(a)[1][0], m[0][1]);
+
+        let v = V::new([1, 2, 3, 4]);
+        let m = v.outer(v);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(m[i][j], v[i] * v[j]);
+            }
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::prelude::*;
+    #[test]
+    fn saturating_cast() {
+        let v = f64x4::new([f64::INFINITY, f64::NEG_INFINITY, f64::NAN, 3e9]);
+        let r: i32x4 = v.saturating_cast();
+        assert_eq!(r, i32x4::new([i32::MAX, i32::MIN, 0, i32::MAX]));
 
-    type V = u16x4;
+        let v = f32x4::new([-3e9, -2.7, 2.7, 2147483520.0]);
+        let r: i32x4 = v.saturating_cast();
+        assert_eq!(r, i32x4::new([i32::MIN, -2, 2, 2147483520]));
+
+        let v = f32x4::new([-1.0, 255.5, 256.0, -0.5]);
+        let r: u8x4 = v.saturating_cast();
+        assert_eq!(r, u8x4::new([0, 255, 255, 0]));
+        for (r, x) in r.iter().zip(v.iter()) {
+            assert_eq!(*r, *x as u8);
+        }
+    }
 
     #[test]
-    #[should_panic(expected = "Creating vector from the wrong sized slice (expected 4, got 3)")]
-    fn wrong_size_new() {
-        V::new([1, 2, 3]);
+    fn is_sorted() {
+        assert!(i32x4::new([-5, 0, 0, 7]).is_sorted());
+        assert!(!i32x4::new([-5, 1, 0, 7]).is_sorted());
+        assert!(!i32x4::new([0, 1, 2, -1]).is_sorted());
+        assert!(i32x4::splat(3).is_sorted());
+        assert!(!f32x2::new([f32::NAN, 1.0]).is_sorted());
+        assert!(i32x4::new([7, 0, 0, -5]).is_sorted_by(|a, b| a >= b));
+        assert!(!i32x4::new([-5, 0, 0, 7]).is_sorted_by(|a, b| a > b));
     }
 
     #[test]
-    fn round_trip() {
-        let orig = [1, 2, 3, 4];
-        assert_eq!(<[u16; 4]>::from(u16x4::from(orig)), orig);
+    fn rotate_lanes() {
+        let v = V::new([1, 2, 3, 4]);
+        assert_eq!(v.rotate_lanes_left(0), v);
+        assert_eq!(v.rotate_lanes_right(0), v);
+        assert_eq!(v.rotate_lanes_left(4), v);
+        assert_eq!(v.rotate_lanes_right(4), v);
+        assert_eq!(v.rotate_lanes_left(1), V::new([2, 3, 4, 1]));
+        assert_eq!(v.rotate_lanes_right(1), V::new([4, 1, 2, 3]));
+        assert_eq!(v.rotate_lanes_left(6), V::new([3, 4, 1, 2]));
+        assert_eq!(v.rotate_lanes_right(7), v.rotate_lanes_left(1));
+
+        let w = u8x16::new((0..16).collect::<Vec<_>>());
+        for n in 0..20 {
+            let mut expected = (0..16).collect::<Vec<u8>>();
+            expected.rotate_left(n % 16);
+            assert_eq!(w.rotate_lanes_left(n), u8x16::new(&expected));
+            assert_eq!(w.rotate_lanes_left(n).rotate_lanes_right(n), w);
+        }
     }
 
     #[test]
-    fn shuffle() {
-        let v1 = V::new([1, 2, 3, 4]);
-        let v2 = V::gather_load(v1, [3, 1, 2, 0]);
-        assert_eq!(v2.deref(), &[4, 2, 3, 1]);
-        let v3 = V::gather_load(v2, [0, 0, 2, 2]);
-        assert_eq!(v3.deref(), &[4, 4, 3, 3]);
+    fn shift_lanes() {
+        let v = V::new([1, 2, 3, 4]);
+        assert_eq!(v.shift_lanes_left(0, 7), v);
+        assert_eq!(v.shift_lanes_right(0, 7), v);
+        assert_eq!(v.shift_lanes_left(1, 7), V::new([2, 3, 4, 7]));
+        assert_eq!(v.shift_lanes_right(1, 7), V::new([7, 1, 2, 3]));
+        assert_eq!(v.shift_lanes_left(2, 0), V::new([3, 4, 0, 0]));
+        assert_eq!(v.shift_lanes_left(4, 7), V::splat(7));
+        assert_eq!(v.shift_lanes_right(4, 7), V::splat(7));
+        assert_eq!(v.shift_lanes_left(100, 7), V::splat(7));
+        assert_eq!(v.shift_lanes_right(usize::MAX, 7), V::splat(7));
     }
 
     #[test]
-    fn gather() {
-        let data = (1..=10).collect::<Vec<_>>();
-        let v = V::gather_load(data, [0, 2, 4, 6]);
-        assert_eq!(v, [1, 3, 5, 7]);
+    fn gather_const() {
+        struct Reverse;
+        impl ConstIndices<4> for Reverse {
+            const IDX: [usize; 4] = [3, 2, 1, 0];
+        }
+        struct Evens;
+        impl ConstIndices<4> for Evens {
+            const IDX: [usize; 4] = [0, 2, 4, 6];
+        }
+
+        let v = V::new([1, 2, 3, 4]);
+        assert_eq!(V::gather_const::<Reverse, 4>(&v), V::new([4, 3, 2, 1]));
+        let r = V::gather_const::<Reverse, 4>(&v);
+        assert_eq!(r, V::gather_load(v, [3, 2, 1, 0]));
+        let data = [10, 11, 12, 13, 14, 15, 16, 17];
+        assert_eq!(V::gather_const::<Evens, 8>(&data), V::new([10, 12, 14, 16]));
+        assert_eq!(
+            V::gather_const::<Reverse, _>(&data),
+            V::new([13, 12, 11, 10])
+        );
     }
 
     #[test]
-    fn scatter() {
+    fn neg_wrapping() {
+        use core::num::Wrapping;
+
+        let min = wi32x2::splat(Wrapping(i32::MIN));
+        assert_eq!(-min, min);
+        let v = wi32x2::new([Wrapping(i32::MAX), Wrapping(-5)]);
+        assert_eq!(-v, wi32x2::new([Wrapping(-i32::MAX), Wrapping(5)]));
+        let small = wi8x16::splat(Wrapping(i8::MIN));
+        assert_eq!(-small, small);
+    }
+
+    #[test]
+    fn sort_lanes_by() {
+        let nan = f32::NAN;
+        let v = f32x8::new([3.0, nan, -1.0, -0.0, 0.0, -nan, 2.5, -7.0]);
+        let sorted = v.sort_lanes_by(f32::total_cmp);
+        let expected = [-nan, -7.0, -1.0, -0.0, 0.0, 2.5, 3.0, nan];
+        for (s, e) in sorted.iter().zip(expected.iter()) {
+            assert_eq!(s.to_bits(), e.to_bits());
+        }
+
+        let v = i32x4::new([3, -1, 4, 1]);
+        assert_eq!(v.sort_lanes_by(|a, b| b.cmp(a)), i32x4::new([4, 3, 1, -1]));
+        let v = u8x16::new((0..16).map(|i| i * 7 % 16).collect::<Vec<_>>());
+        assert_eq!(v.sort_lanes_by(Ord::cmp), v.sort_lanes());
+    }
+
+    #[test]
+    fn select_nth_lane() {
+        let v = u8x8::new([9, 3, 200, 3, 0, 17, 255, 42]);
+        let mut sorted = v.to_vec();
+        sorted.sort_unstable();
+        for (k, s) in sorted.iter().enumerate() {
+            assert_eq!(v.select_nth_lane(k), *s);
+        }
+        assert_eq!(v.select_nth_lane(1), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Selecting lane 4 out of 4")]
+    fn select_nth_lane_out_of_bounds() {
+        V::splat(1).select_nth_lane(4);
+    }
+
+    #[test]
+    fn sum_product_refs() {
+        let vecs = [
+            V::new([1, 2, 3, 4]),
+            V::new([2, 2, 2, 2]),
+            V::new([0, 1, 5, 1]),
+        ];
+        assert_eq!(vecs.iter().sum::<V>(), V::new([3, 5, 10, 7]));
+        assert_eq!(vecs.iter().product::<V>(), V::new([0, 4, 30, 8]));
+        assert_eq!(vecs.iter().sum::<V>(), vecs.iter().copied().sum::<V>());
+        assert_eq!([].iter().sum::<V>(), V::default());
+        assert_eq!([].iter().product::<V>(), V::splat(1));
+    }
+
+    #[test]
+    fn fold() {
         let v = V::new([1, 2, 3, 4]);
-        let mut output = [0; 10];
-        v.scatter_store(&mut output, [1, 3, 5, 7]);
-        assert_eq!(output, [0, 1, 0, 2, 0, 3, 0, 4, 0, 0]);
+        assert_eq!(v.fold(0usize, |acc, lane| acc * 10 + lane as usize), 1234);
+        assert_eq!(v.fold(0, |acc, lane| acc + lane), v.horizontal_sum());
     }
 
     #[test]
-    #[should_panic(expected = "Gather out of bounds")]
-    fn gather_oob() {
-        V::gather_load([1, 2, 3], [0, 1, 2, 3]);
+    fn enumerate_lanes() {
+        let v = V::new([5, 6, 7, 8]);
+        let lanes = v.enumerate_lanes().collect::<Vec<_>>();
+        assert_eq!(lanes, [(0, 5), (1, 6), (2, 7), (3, 8)]);
     }
 
     #[test]
-    #[should_panic(expected = "Gathering vector from wrong number of indexes")]
-    fn gather_idx_cnt() {
-        V::gather_load([0, 1, 2, 3, 4], [0, 1]);
+    fn mul_widen() {
+        let v = u16x2::splat(0xFFFF);
+        assert_eq!(v.mul_widen::<u32x2>(v), u32x2::splat(0xFFFE0001));
+
+        let v = i8x4::new([-128, 127, -1, 0]);
+        let o = i8x4::new([-128, 127, 100, 5]);
+        assert_eq!(v.mul_widen::<i16x4>(o), i16x4::new([16384, 16129, -100, 0]));
+
+        let v = u64x2::splat(u64::MAX);
+        let wide: Vector<Align1, u128, 2> = v.mul_widen(v);
+        assert_eq!(wide[0], u64::MAX as u128 * u64::MAX as u128);
     }
 
     #[test]
-    #[should_panic(expected = "Scatter out of bounds")]
-    fn scatter_oob() {
-        let mut out = [0; 10];
-        V::new([1, 2, 3, 4]).scatter_store(&mut out, [0, 1, 2, 15]);
+    fn mul_hi() {
+        let v = u16x4::new([0xFFFF, 0x8000, 3, 0]);
+        let o = u16x4::new([0xFFFF, 4, 5, 0xFFFF]);
+        assert_eq!(v.mul_hi(o), u16x4::new([0xFFFE, 2, 0, 0]));
+
+        let v = i32x4::new([-1, i32::MIN, i32::MAX, 1 << 20]);
+        let o = i32x4::new([1, i32::MIN, -2, 1 << 20]);
+        assert_eq!(v.mul_hi(o), i32x4::new([-1, 1 << 30, -1, 1 << 8]));
+
+        let v = u64x2::splat(u64::MAX);
+        assert_eq!(v.mul_hi(v), u64x2::splat(u64::MAX - 1));
     }
 
     #[test]
-    #[should_panic(expected = "Scattering vector to wrong number of indexes")]
-    fn scatter_idx_cnt() {
-        let mut out = [0; 10];
-        V::new([1, 2, 3, 4]).scatter_store(&mut out, [0, 1, 2]);
+    fn wrapping_neg_abs() {
+        let v = i32x2::new([i32::MIN, 5]);
+        assert_eq!(v.wrapping_neg(), i32x2::new([i32::MIN, -5]));
+        assert_eq!(v.wrapping_abs(), i32x2::new([i32::MIN, 5]));
+        let v = i64x2::new([-1, i64::MAX]);
+        assert_eq!(v.wrapping_neg(), i64x2::new([1, -i64::MAX]));
+        assert_eq!(v.wrapping_abs(), i64x2::new([1, i64::MAX]));
     }
 
-    // TODO: Tests for out of bounds index on masked loads/stores + tests for index out of bound
-    // but disabled by the mask
+    #[test]
+    fn clamp_scalar() {
+        let v = i32x4::new([-1, 0, 5, i32::MAX]);
+        assert_eq!(v.clamp_scalar(0, 4), i32x4::new([0, 0, 4, 4]));
+        assert_eq!(v.clamp_scalar(-1, -1), i32x4::splat(-1));
+    }
 
-    const T: m32 = m32::TRUE;
-    const F: m32 = m32::FALSE;
+    #[test]
+    #[should_panic(expected = "Clamping with lower bound above the upper bound")]
+    fn clamp_scalar_inverted() {
+        u32x4::splat(1).clamp_scalar(3, 2);
+    }
 
     #[test]
-    fn cmp() {
-        let v1 = u32x4::new([1, 3, 5, 7]);
-        let v2 = u32x4::new([2, 3, 4, 5]);
+    fn select_cmp_sign() {
+        fn sign(v: f32x4) -> f32x4 {
+            let z = f32x4::splat(0.0);
+            v.select_cmp(z, f32x4::splat(-1.0), z, f32x4::splat(1.0))
+        }
+        let s = sign(f32x4::new([-3.5, 0.0, 1e-20, -0.0]));
+        assert_eq!(s, f32x4::new([-1.0, 0.0, 1.0, 0.0]));
+        // NaN is neither smaller nor bigger, so it gets the „equal“ value
+        assert_eq!(sign(f32x4::splat(f32::NAN)), f32x4::splat(0.0));
+    }
 
-        assert_eq!(v1.eq(v2), m32x4::new([F, T, F, F]));
-        assert_eq!(v1.le(v2), m32x4::new([T, T, F, F]));
-        assert_eq!(v1.ge(v2), m32x4::new([F, T, T, T]));
+    #[test]
+    fn hypot() {
+        let x = f64x4::new([1.0, -2.5, 0.0, 7.0]);
+        let y = f64x4::new([1.0, 4.0, -3.0, 0.5]);
+        let naive = (x * x + y * y).iter().map(|l| l.sqrt()).collect::<Vec<_>>();
+        for (h, n) in x.hypot(y).iter().zip(naive) {
+            assert!((h - n).abs() < 1e-12);
+        }
+
+        let big = f32x4::splat(1e30);
+        assert!((big * big).iter().all(|l| l.is_infinite()));
+        let h = big.hypot(big);
+        assert!(h.iter().all(|&l| (l - 1e30 * 2f32.sqrt()).abs() < 1e24));
     }
 
     #[test]
-    fn blend() {
-        let v1 = u32x4::new([1, 2, 3, 4]);
-        let v2 = u32x4::new([5, 6, 7, 8]);
+    fn atan2() {
+        use core::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
 
-        let b1 = v1.blend(v2, m32x4::new([F, T, F, T]));
-        assert_eq!(b1, u32x4::new([1, 6, 3, 8]));
+        let y = f64x4::new([1.0, 1.0, -1.0, 0.0]);
+        let x = f64x4::new([1.0, 0.0, -1.0, -1.0]);
+        let expected = [FRAC_PI_4, FRAC_PI_2, -3.0 * FRAC_PI_4, PI];
+        for (a, e) in y.atan2(x).iter().zip(&expected) {
+            assert!((a - e).abs() < 1e-12, "{} vs {}", a, e);
+        }
+    }
 
-        let b2 = v1.blend(v2, [false, true, false, true]);
-        assert_eq!(b1, b2);
+    #[test]
+    fn cosine_similarity() {
+        let a = f64x4::new([1.0, -2.0, 0.5, 3.0]);
+        let close = |x: f64, y: f64| (x - y).abs() < 1e-12;
+        assert!(close(a.cosine_similarity(a * f64x4::splat(2.5)), 1.0));
+        assert!(close(a.cosine_similarity(-a), -1.0));
+        let orthogonal = f64x4::new([2.0, 1.0, 0.0, 0.0]);
+        assert!(close(a.cosine_similarity(orthogonal), 0.0));
+        assert_eq!(f64x4::default().cosine_similarity(a), 0.0);
+
+        // The squared norms would overflow/underflow, the norms themselves don't.
+        let close = |x: f32| (x - 1.0).abs() < 1e-6;
+        let huge = f32x2::new([6e9, 8e9]);
+        assert!(close(huge.cosine_similarity(huge * f32x2::splat(0.5))));
+        let tiny = f32x2::new([6e-13, 8e-13]);
+        assert!(close(tiny.cosine_similarity(tiny * f32x2::splat(2.0))));
+    }
+
+    #[test]
+    fn distance() {
+        let a = f32x4::new([0.0, 0.0, 0.0, 0.0]);
+        let b = f32x4::new([1.0, 2.0, 2.0, 0.0]);
+        assert_eq!(a.distance_sq(b), 9.0);
+        assert_eq!(b.distance_sq(a), 9.0);
+        assert_eq!(a.distance(b), 3.0);
+        assert_eq!(b.distance(b), 0.0);
+    }
+
+    #[test]
+    fn kahan() {
+        let v = f32x8::new([1e8, 1.0, -1e8, 1.0, 1e8, 1.0, -1e8, 1.0]);
+        assert_eq!(v.horizontal_sum_kahan(), 4.0);
+        assert_ne!(v.horizontal_sum(), 4.0);
+
+        let v = f32x4::new([0.5, 1.5, 2.5, 3.5]);
+        assert_eq!(v.horizontal_sum_kahan(), v.horizontal_sum());
     }
 
     #[test]